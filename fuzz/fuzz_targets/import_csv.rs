@@ -0,0 +1,20 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use simbad::{import_objects, import_with_options, ImportOptions, NumberFormat, RecordFile};
+
+// The importers read from a path, so each input goes through a scratch file.
+fuzz_target!(|data: &[u8]| {
+    let path = std::env::temp_dir().join(format!("simbad-fuzz-{}.csv", std::process::id()));
+    if std::fs::write(&path, data).is_err() {
+        return;
+    }
+    for numbers in [NumberFormat::Strict, NumberFormat::Tolerant] {
+        let _ = import_with_options(&path, &ImportOptions::new().numbers(numbers));
+    }
+    let _ = import_objects(&path);
+    if let Ok(mut file) = RecordFile::open(&path) {
+        let len = file.len();
+        let _ = file.rows(0..len);
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use simbad::Catalog;
+
+// Truncated or corrupt catalogs, including counts and string lengths far beyond the data, must
+// come back as errors without panicking or allocating what the header claims.
+fuzz_target!(|data: &[u8]| {
+    let path = std::env::temp_dir().join(format!("simbad-fuzz-{}.bin", std::process::id()));
+    if std::fs::write(&path, data).is_err() {
+        return;
+    }
+    let _ = Catalog::load_binary(&path);
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use simbad::{parse_decimal_degrees, parse_equatorial, parse_sexagesimal, Degree, HourAngle, ProperMotion, RaUnit};
+
+fuzz_target!(|input: &str| {
+    let _ = parse_sexagesimal(input);
+    let _ = parse_equatorial(input, RaUnit::Hours);
+    let _ = parse_equatorial(input, RaUnit::Degrees);
+    let _ = parse_decimal_degrees(input);
+    let _ = input.parse::<Degree>();
+    let _ = input.parse::<HourAngle>();
+    let _ = input.parse::<ProperMotion>();
+});
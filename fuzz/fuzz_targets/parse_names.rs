@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use simbad::spectral::{estimate_distance_spectroscopic, SpectralType};
+use simbad::{designation, identifier, Constellation, DisplayNameStyle, Identifier, QueryManifest};
+
+fuzz_target!(|input: &str| {
+    let _ = input.parse::<SpectralType>();
+    let _ = estimate_distance_spectroscopic(input, 5.);
+    let _ = input.parse::<Constellation>();
+    let _ = Identifier::new(input).in_catalog("HD");
+    let _ = identifier::split_component(input);
+    let _ = designation::parse_designation(input);
+    for style in [DisplayNameStyle::Unicode, DisplayNameStyle::SpelledOut, DisplayNameStyle::Abbreviation] {
+        let _ = designation::convert_greek(input, style);
+    }
+    let _ = QueryManifest::from_toml(input);
+});
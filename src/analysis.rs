@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+use crate::{Band, Star};
+use crate::spectral::SpectralClass;
+
+// Width of the apparent V magnitude bins used for the luminosity function fit.
+const BIN_WIDTH: f32 = 0.5;
+
+#[derive(Default, Clone, Debug)]
+pub struct ClassDistribution {
+    pub total: usize,
+    // Stars without a parseable O..M spectral type.
+    pub unclassified: usize,
+    pub counts: BTreeMap<SpectralClass, usize>,
+    // Fractions of the classified stars, summing to 1.
+    pub fractions: BTreeMap<SpectralClass, f32>,
+    pub luminosity_function: Option<PowerLawFit>
+}
+
+// Least-squares fit of log10 N(<m) = intercept + slope * m on the cumulative apparent V counts.
+// A complete sample of uniformly distributed stars gives slope 0.6; a flatter slope at the faint
+// end usually means the sample runs out of completeness there.
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub struct PowerLawFit {
+    pub slope: f32,
+    pub intercept: f32,
+    // Number of magnitude bins used in the fit.
+    pub bins: usize
+}
+
+impl PowerLawFit {
+    // Expected number of stars brighter than `magnitude`.
+    pub fn cumulative_count(&self, magnitude: f32) -> f32 {
+        10f32.powf(self.intercept + self.slope * magnitude)
+    }
+}
+
+pub fn class_distribution<'a, I: IntoIterator<Item = &'a Star>>(stars: I) -> ClassDistribution {
+    let mut distribution = ClassDistribution::default();
+    let mut magnitudes = Vec::new();
+    for star in stars {
+        distribution.total += 1;
+        match star.spectral_type() {
+            Some(spec_type) => *distribution.counts.entry(spec_type.class).or_insert(0) += 1,
+            None => distribution.unclassified += 1
+        }
+        magnitudes.extend(star.photometry.get(Band::V).filter(|v| v.is_finite()));
+    }
+    let classified = distribution.total - distribution.unclassified;
+    distribution.fractions = distribution.counts.iter()
+        .map(|(class, count)| (*class, *count as f32 / classified as f32))
+        .collect();
+    distribution.luminosity_function = fit_luminosity_function(&mut magnitudes);
+    distribution
+}
+
+// Needs at least three non-empty bins to say anything about the slope.
+pub fn fit_luminosity_function(magnitudes: &mut [f32]) -> Option<PowerLawFit> {
+    magnitudes.sort_by(f32::total_cmp);
+    let (first, last) = (*magnitudes.first()?, *magnitudes.last()?);
+    let points = (1..)
+        .map(|bin| first + bin as f32 * BIN_WIDTH)
+        .map(|edge| (edge, magnitudes.partition_point(|m| *m < edge)))
+        .take_while(|(edge, _)| *edge - BIN_WIDTH <= last)
+        .map(|(edge, count)| (edge, (count as f32).log10()))
+        .collect::<Vec<_>>();
+    if points.len() < 3 {
+        return None;
+    }
+    let n = points.len() as f32;
+    let mean_m = points.iter().map(|(m, _)| m).sum::<f32>() / n;
+    let mean_n = points.iter().map(|(_, log_n)| log_n).sum::<f32>() / n;
+    let covariance = points.iter().map(|(m, log_n)| (m - mean_m) * (log_n - mean_n)).sum::<f32>();
+    let variance = points.iter().map(|(m, _)| (m - mean_m).powi(2)).sum::<f32>();
+    let slope = covariance / variance;
+    Some(PowerLawFit {
+        slope,
+        intercept: mean_n - slope * mean_m,
+        bins: points.len()
+    })
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct MagnitudeCompleteness {
+    // (lower bin edge, count) in bins of `BIN_WIDTH` magnitudes.
+    pub histogram: Vec<(f32, usize)>,
+    // Upper edge of the most populated bin: fainter than this counts stop growing, so the sample
+    // is incomplete. None without enough data.
+    pub limit: Option<f32>,
+    // Counts stop at the turnover or fall off a cliff after it (to a quarter of the peak within
+    // one bin), which points at a magnitude cut in the query rather than a gradual detection limit.
+    pub truncated: bool
+}
+
+pub fn magnitude_completeness<'a, I: IntoIterator<Item = &'a Star>>(stars: I, band: Band) -> MagnitudeCompleteness {
+    let mut magnitudes = stars.into_iter().filter_map(|s| s.photometry.get(band)).filter(|m| m.is_finite()).collect::<Vec<_>>();
+    magnitudes.sort_by(f32::total_cmp);
+    let (Some(&first), Some(&last)) = (magnitudes.first(), magnitudes.last()) else { return MagnitudeCompleteness::default() };
+    let start = (first / BIN_WIDTH).floor() * BIN_WIDTH;
+    let bins = ((last - start) / BIN_WIDTH).floor() as usize + 1;
+    let mut histogram = (0..bins).map(|i| (start + i as f32 * BIN_WIDTH, 0)).collect::<Vec<_>>();
+    for m in &magnitudes {
+        histogram[(((m - start) / BIN_WIDTH) as usize).min(bins - 1)].1 += 1;
+    }
+    // Ties go to the fainter bin so a flat top doesn't report the limit too early.
+    let peak = histogram.iter().enumerate().fold(0, |peak, (i, bin)| if bin.1 >= histogram[peak].1 { i } else { peak });
+    if bins < 3 || peak == 0 {
+        return MagnitudeCompleteness { histogram, limit: None, truncated: false };
+    }
+    let peak_count = histogram[peak].1;
+    let truncated = histogram.get(peak + 1).is_none_or(|next| next.1 * 4 <= peak_count);
+    MagnitudeCompleteness {
+        limit: Some(histogram[peak].0 + BIN_WIDTH),
+        histogram,
+        truncated
+    }
+}
@@ -0,0 +1,33 @@
+use std::f32::consts::{PI, TAU};
+
+pub trait Angle {
+    // Wraps radians into 0..2π.
+    fn wrap_positive(self) -> Self;
+    // Wraps radians into -π..π.
+    fn wrap_signed(self) -> Self;
+}
+
+impl Angle for f32 {
+    fn wrap_positive(self) -> Self {
+        let wrapped = self.rem_euclid(TAU);
+        // rem_euclid can round up to exactly TAU for tiny negative inputs
+        if wrapped >= TAU { 0. } else { wrapped }
+    }
+
+    fn wrap_signed(self) -> Self {
+        let wrapped = self.wrap_positive();
+        if wrapped >= PI { wrapped - TAU } else { wrapped }
+    }
+}
+
+impl Angle for f64 {
+    fn wrap_positive(self) -> Self {
+        let wrapped = self.rem_euclid(std::f64::consts::TAU);
+        if wrapped >= std::f64::consts::TAU { 0. } else { wrapped }
+    }
+
+    fn wrap_signed(self) -> Self {
+        let wrapped = self.wrap_positive();
+        if wrapped >= std::f64::consts::PI { wrapped - std::f64::consts::TAU } else { wrapped }
+    }
+}
@@ -0,0 +1,234 @@
+use std::io::{Read, Write};
+use glam::Vec3;
+use std::path::PathBuf;
+use crate::{Band, CoordinateUncertainty, Identifier, Photometry, Provenance, ProperMotion, SimbadError, Source, Star, Variability};
+
+pub(crate) const MAGIC: &[u8; 8] = b"SMBDCAT\0";
+pub(crate) const FORMAT_VERSION: u16 = 10;
+
+pub(crate) fn write_header<W: Write>(w: &mut W, count: usize) -> std::io::Result<()> {
+    w.write_all(MAGIC)?;
+    w.write_all(&FORMAT_VERSION.to_le_bytes())?;
+    w.write_all(&(count as u64).to_le_bytes())
+}
+
+pub(crate) fn read_header<R: Read>(r: &mut R) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut magic = [0u8; 8];
+    r.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(SimbadError::InvalidFormat.into());
+    }
+    let version = read_u16(r)?;
+    if version != FORMAT_VERSION {
+        return Err(SimbadError::UnsupportedVersion(version).into());
+    }
+    Ok(read_u64(r)? as usize)
+}
+
+pub(crate) fn write_star<W: Write>(w: &mut W, star: &Star) -> std::io::Result<()> {
+    w.write_all(&(star.id as u64).to_le_bytes())?;
+    for v in star.pos.to_array() {
+        w.write_all(&v.to_le_bytes())?;
+    }
+    match star.coord_uncertainty {
+        Some(err) => {
+            w.write_all(&[1])?;
+            for v in [err.maj_mas, err.min_mas, err.pa_deg] {
+                w.write_all(&v.to_le_bytes())?;
+            }
+        }
+        None => w.write_all(&[0])?
+    }
+    write_str(w, &star.identifier)?;
+    w.write_all(&(star.aliases.len() as u32).to_le_bytes())?;
+    for alias in &star.aliases {
+        write_str(w, alias.as_str())?;
+    }
+    write_str(w, &star.name)?;
+    write_str(w, &star.class)?;
+    write_str(w, star.constellation.map_or("", |c| c.abbreviation()))?;
+    write_photometry(w, &star.photometry)?;
+    match star.proper_motion {
+        Some(pm) => {
+            w.write_all(&[1])?;
+            w.write_all(&pm.ra.to_le_bytes())?;
+            w.write_all(&pm.dec.to_le_bytes())?;
+        }
+        None => w.write_all(&[0])?
+    }
+    write_option_f32(w, star.redshift)?;
+    write_option_f32(w, star.radial_velocity)?;
+    match &star.variability {
+        Some(variability) => {
+            w.write_all(&[1])?;
+            write_str(w, &variability.var_type)?;
+            write_option_f32(w, variability.period_days)?;
+            write_option_f32(w, variability.amplitude)?;
+        }
+        None => w.write_all(&[0])?
+    }
+    w.write_all(&star.epoch.to_le_bytes())?;
+    w.write_all(&(star.provenance.len() as u32).to_le_bytes())?;
+    for provenance in &star.provenance {
+        write_provenance(w, provenance)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn read_star<R: Read>(r: &mut R) -> std::io::Result<Star> {
+    Ok(Star {
+        id: read_u64(r)? as usize,
+        pos: Vec3::new(read_f32(r)?, read_f32(r)?, read_f32(r)?),
+        coord_uncertainty: match read_u8(r)? {
+            0 => None,
+            _ => Some(CoordinateUncertainty::new(read_f32(r)?, read_f32(r)?, read_f32(r)?))
+        },
+        identifier: read_str(r)?,
+        aliases: (0..read_u32(r)?).map(|_| read_str(r).map(|s| Identifier::new(&s))).collect::<std::io::Result<_>>()?,
+        name: read_str(r)?,
+        class: read_str(r)?,
+        constellation: read_str(r)?.parse().ok(),
+        photometry: read_photometry(r)?,
+        proper_motion: match read_u8(r)? {
+            0 => None,
+            _ => Some(ProperMotion::new(read_f32(r)?, read_f32(r)?))
+        },
+        redshift: read_option_f32(r)?,
+        radial_velocity: read_option_f32(r)?,
+        variability: match read_u8(r)? {
+            0 => None,
+            _ => Some(Variability { var_type: read_str(r)?, period_days: read_option_f32(r)?, amplitude: read_option_f32(r)? })
+        },
+        epoch: read_f64(r)?,
+        provenance: (0..read_u32(r)?).map(|_| read_provenance(r)).collect::<std::io::Result<_>>()?,
+    })
+}
+
+fn write_provenance<W: Write>(w: &mut W, provenance: &Provenance) -> std::io::Result<()> {
+    match &provenance.source {
+        Source::File(path) => {
+            w.write_all(&[0])?;
+            write_str(w, &path.to_string_lossy())?;
+        }
+        Source::Query(query) => {
+            w.write_all(&[1])?;
+            write_str(w, query)?;
+        }
+        Source::Catalog(name) => {
+            w.write_all(&[2])?;
+            write_str(w, name)?;
+        }
+    }
+    match provenance.row {
+        Some(row) => {
+            w.write_all(&[1])?;
+            w.write_all(&row.to_le_bytes())
+        }
+        None => w.write_all(&[0])
+    }
+}
+
+fn read_provenance<R: Read>(r: &mut R) -> std::io::Result<Provenance> {
+    let source = match read_u8(r)? {
+        0 => Source::File(PathBuf::from(read_str(r)?)),
+        1 => Source::Query(read_str(r)?),
+        2 => Source::Catalog(read_str(r)?),
+        tag => return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("unknown provenance source {}", tag)))
+    };
+    let row = match read_u8(r)? {
+        0 => None,
+        _ => Some(read_u64(r)?)
+    };
+    Ok(Provenance::new(source, row))
+}
+
+fn write_photometry<W: Write>(w: &mut W, photometry: &Photometry) -> std::io::Result<()> {
+    let bands = Band::ALL.map(|band| photometry.get(band));
+    let mask = bands.iter().enumerate().fold(0u16, |mask, (i, m)| if m.is_some() { mask | 1 << i } else { mask });
+    w.write_all(&mask.to_le_bytes())?;
+    for m in bands.into_iter().flatten() {
+        w.write_all(&m.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn read_photometry<R: Read>(r: &mut R) -> std::io::Result<Photometry> {
+    let mask = read_u16(r)?;
+    let mut photometry = Photometry::default();
+    for (i, band) in Band::ALL.into_iter().enumerate() {
+        if mask & 1 << i != 0 {
+            photometry.set(band, Some(read_f32(r)?));
+        }
+    }
+    Ok(photometry)
+}
+
+fn write_option_f32<W: Write>(w: &mut W, value: Option<f32>) -> std::io::Result<()> {
+    match value {
+        Some(v) => {
+            w.write_all(&[1])?;
+            w.write_all(&v.to_le_bytes())
+        }
+        None => w.write_all(&[0])
+    }
+}
+
+fn read_option_f32<R: Read>(r: &mut R) -> std::io::Result<Option<f32>> {
+    Ok(match read_u8(r)? {
+        0 => None,
+        _ => Some(read_f32(r)?)
+    })
+}
+
+fn write_str<W: Write>(w: &mut W, s: &str) -> std::io::Result<()> {
+    w.write_all(&(s.len() as u32).to_le_bytes())?;
+    w.write_all(s.as_bytes())
+}
+
+// The length comes from the file, so the buffer grows with what is actually there instead of
+// being allocated up front: a corrupt length fails with `UnexpectedEof` rather than aborting.
+fn read_str<R: Read>(r: &mut R) -> std::io::Result<String> {
+    let len = read_u32(r)? as u64;
+    let mut bytes = vec![];
+    r.take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(std::io::ErrorKind::UnexpectedEof.into());
+    }
+    String::from_utf8(bytes).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+fn read_u8<R: Read>(r: &mut R) -> std::io::Result<u8> {
+    let mut buf = [0u8];
+    r.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+fn read_u16<R: Read>(r: &mut R) -> std::io::Result<u16> {
+    let mut buf = [0u8; 2];
+    r.read_exact(&mut buf)?;
+    Ok(u16::from_le_bytes(buf))
+}
+
+fn read_u32<R: Read>(r: &mut R) -> std::io::Result<u32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(r: &mut R) -> std::io::Result<u64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_f32<R: Read>(r: &mut R) -> std::io::Result<f32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(f32::from_le_bytes(buf))
+}
+
+fn read_f64<R: Read>(r: &mut R) -> std::io::Result<f64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(f64::from_le_bytes(buf))
+}
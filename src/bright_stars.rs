@@ -0,0 +1,340 @@
+use crate::{parse_sexagesimal, Band, Photometry, Provenance, Source, Star, StellarPosition};
+
+// (name, constellation, J2000 position, V magnitude, spectral type, distance in ly); the stars
+// brighter than about V = 3.6. `Star::id` is the index here, so new entries go at the end.
+// Some magnitudes are 3.14, which clippy takes for π.
+#[allow(clippy::approx_constant)]
+const BRIGHT_STARS: &[(&str, &str, &str, f32, &str, f32)] = &[
+    ("Sirius", "CMa", "06 45 08.92 -16 42 58.0", -1.46, "A1V", 8.6),
+    ("Canopus", "Car", "06 23 57.11 -52 41 44.4", -0.74, "A9II", 310.),
+    ("Rigil Kentaurus", "Cen", "14 39 36.49 -60 50 02.4", -0.27, "G2V", 4.37),
+    ("Arcturus", "Boo", "14 15 39.67 +19 10 56.7", -0.05, "K1.5III", 36.7),
+    ("Vega", "Lyr", "18 36 56.34 +38 47 01.3", 0.03, "A0V", 25.0),
+    ("Capella", "Aur", "05 16 41.36 +45 59 52.8", 0.08, "G3III", 42.9),
+    ("Rigel", "Ori", "05 14 32.27 -08 12 05.9", 0.13, "B8Ia", 860.),
+    ("Procyon", "CMi", "07 39 18.12 +05 13 30.0", 0.37, "F5IV-V", 11.5),
+    ("Achernar", "Eri", "01 37 42.85 -57 14 12.3", 0.46, "B6Vep", 139.),
+    ("Betelgeuse", "Ori", "05 55 10.31 +07 24 25.4", 0.50, "M1-2Ia", 548.),
+    ("Hadar", "Cen", "14 03 49.40 -60 22 22.9", 0.61, "B1III", 390.),
+    ("Altair", "Aql", "19 50 47.00 +08 52 06.0", 0.76, "A7V", 16.7),
+    ("Acrux", "Cru", "12 26 35.90 -63 05 56.7", 0.76, "B0.5IV", 320.),
+    ("Aldebaran", "Tau", "04 35 55.24 +16 30 33.5", 0.86, "K5III", 65.3),
+    ("Antares", "Sco", "16 29 24.46 -26 25 55.2", 0.96, "M1.5Iab", 550.),
+    ("Spica", "Vir", "13 25 11.58 -11 09 40.8", 0.97, "B1III-IV", 250.),
+    ("Pollux", "Gem", "07 45 18.95 +28 01 34.3", 1.14, "K0III", 33.8),
+    ("Fomalhaut", "PsA", "22 57 39.05 -29 37 20.1", 1.16, "A3V", 25.1),
+    ("Deneb", "Cyg", "20 41 25.92 +45 16 49.2", 1.25, "A2Ia", 2600.),
+    ("Mimosa", "Cru", "12 47 43.27 -59 41 19.6", 1.25, "B0.5III", 280.),
+    ("Regulus", "Leo", "10 08 22.31 +11 58 02.0", 1.40, "B8IVn", 79.3),
+    ("Adhara", "CMa", "06 58 37.55 -28 58 19.5", 1.50, "B2II", 430.),
+    ("Castor", "Gem", "07 34 35.86 +31 53 17.8", 1.58, "A1V", 51.),
+    ("Shaula", "Sco", "17 33 36.52 -37 06 13.8", 1.62, "B2IV", 570.),
+    ("Gacrux", "Cru", "12 31 09.96 -57 06 47.6", 1.64, "M3.5III", 88.6),
+    ("Bellatrix", "Ori", "05 25 07.86 +06 20 59.0", 1.64, "B2III", 250.),
+    ("Elnath", "Tau", "05 26 17.51 +28 36 26.8", 1.65, "B7III", 134.),
+    ("Miaplacidus", "Car", "09 13 11.98 -69 43 01.9", 1.67, "A1III", 113.),
+    ("Alnilam", "Ori", "05 36 12.81 -01 12 06.9", 1.69, "B0Ia", 2000.),
+    ("Alnair", "Gru", "22 08 13.98 -46 57 39.5", 1.74, "B6V", 101.),
+    ("Alnitak", "Ori", "05 40 45.53 -01 56 33.3", 1.77, "O9.5Ib", 1260.),
+    ("Alioth", "UMa", "12 54 01.75 +55 57 35.4", 1.77, "A1III-IVp", 82.6),
+    ("Dubhe", "UMa", "11 03 43.67 +61 45 03.7", 1.79, "K0III", 123.),
+    ("Mirfak", "Per", "03 24 19.37 +49 51 40.2", 1.79, "F5Ib", 510.),
+    ("Regor", "Vel", "08 09 31.95 -47 20 11.7", 1.83, "WC8+O7.5", 1100.),
+    ("Wezen", "CMa", "07 08 23.48 -26 23 35.5", 1.84, "F8Ia", 1600.),
+    ("Kaus Australis", "Sgr", "18 24 10.32 -34 23 04.6", 1.85, "B9.5III", 143.),
+    ("Avior", "Car", "08 22 30.84 -59 30 34.1", 1.86, "K3III", 630.),
+    ("Alkaid", "UMa", "13 47 32.44 +49 18 47.8", 1.86, "B3V", 104.),
+    ("Sargas", "Sco", "17 37 19.13 -42 59 52.2", 1.87, "F1II", 300.),
+    ("Menkalinan", "Aur", "05 59 31.72 +44 56 50.8", 1.90, "A1IV", 81.),
+    ("Atria", "TrA", "16 48 39.90 -69 01 39.8", 1.91, "K2IIb", 391.),
+    ("Alhena", "Gem", "06 37 42.71 +16 23 57.4", 1.92, "A1IV", 109.),
+    ("Peacock", "Pav", "20 25 38.86 -56 44 06.3", 1.94, "B3V", 179.),
+    ("Alsephina", "Vel", "08 44 42.23 -54 42 31.8", 1.96, "A1V", 80.6),
+    ("Mirzam", "CMa", "06 22 41.99 -17 57 21.3", 1.98, "B1II-III", 490.),
+    ("Alphard", "Hya", "09 27 35.24 -08 39 31.0", 1.98, "K3II-III", 177.),
+    ("Polaris", "UMi", "02 31 49.09 +89 15 50.8", 1.98, "F7Ib", 433.),
+    ("Hamal", "Ari", "02 07 10.41 +23 27 44.7", 2.01, "K2III", 65.8),
+    ("Diphda", "Cet", "00 43 35.37 -17 59 11.8", 2.04, "G9III", 96.),
+    ("Nunki", "Sgr", "18 55 15.93 -26 17 48.2", 2.05, "B2.5V", 228.),
+    ("Mirach", "And", "01 09 43.92 +35 37 14.0", 2.05, "M0III", 197.),
+    ("Menkent", "Cen", "14 06 40.95 -36 22 11.8", 2.06, "K0III", 59.),
+    ("Alpheratz", "And", "00 08 23.26 +29 05 25.6", 2.06, "B8IVp", 97.),
+    ("Rasalhague", "Oph", "17 34 56.07 +12 33 36.1", 2.07, "A5III", 48.6),
+    ("Algieba", "Leo", "10 19 58.35 +19 50 29.4", 2.08, "K1III", 130.),
+    ("Kochab", "UMi", "14 50 42.33 +74 09 19.8", 2.08, "K4III", 131.),
+    ("Saiph", "Ori", "05 47 45.39 -09 40 10.6", 2.09, "B0.5Ia", 650.),
+    ("Almach", "And", "02 03 53.95 +42 19 47.0", 2.10, "K3IIb", 350.),
+    ("Algol", "Per", "03 08 10.13 +40 57 20.3", 2.12, "B8V", 90.),
+    ("Denebola", "Leo", "11 49 03.58 +14 34 19.4", 2.14, "A3V", 36.),
+    ("Suhail", "Vel", "09 07 59.76 -43 25 57.3", 2.21, "K4Ib-IIa", 545.),
+    ("Alphecca", "CrB", "15 34 41.27 +26 42 52.9", 2.22, "A1IV", 75.),
+    ("Mintaka", "Ori", "05 32 00.40 -00 17 56.7", 2.23, "O9.5II", 1200.),
+    ("Mizar", "UMa", "13 23 55.54 +54 55 31.3", 2.23, "A2V", 82.9),
+    ("Sadr", "Cyg", "20 22 13.70 +40 15 24.0", 2.23, "F8Ib", 1800.),
+    ("Eltanin", "Dra", "17 56 36.37 +51 29 20.0", 2.23, "K5III", 154.),
+    ("Schedar", "Cas", "00 40 30.44 +56 32 14.4", 2.24, "K0IIIa", 228.),
+    ("Aspidiske", "Car", "09 17 05.41 -59 16 30.8", 2.25, "A9Ib", 690.),
+    ("Naos", "Pup", "08 03 35.05 -40 00 11.3", 2.25, "O4If", 1080.),
+    ("Caph", "Cas", "00 09 10.69 +59 08 59.2", 2.28, "F2III", 54.7),
+    ("Dschubba", "Sco", "16 00 20.01 -22 37 18.1", 2.29, "B0.3IV", 440.),
+    ("Alpha Lupi", "Lup", "14 41 55.76 -47 23 17.5", 2.30, "B1.5III", 460.),
+    ("Eta Centauri", "Cen", "14 35 30.42 -42 09 28.2", 2.35, "B1.5IVpne", 306.),
+    ("Merak", "UMa", "11 01 50.48 +56 22 56.7", 2.37, "A1IV", 79.7),
+    ("Izar", "Boo", "14 44 59.22 +27 04 27.2", 2.37, "K0II-III", 203.),
+    ("Enif", "Peg", "21 44 11.16 +09 52 30.0", 2.39, "K2Ib", 690.),
+    ("Ankaa", "Phe", "00 26 17.05 -42 18 21.6", 2.40, "K0.5IIIb", 85.),
+    ("Scheat", "Peg", "23 03 46.46 +28 04 58.0", 2.42, "M2.5II-III", 196.),
+    ("Sabik", "Oph", "17 10 22.69 -15 43 29.7", 2.43, "A1V", 88.),
+    ("Phecda", "UMa", "11 53 49.85 +53 41 41.1", 2.44, "A0Ve", 83.2),
+    ("Alderamin", "Cep", "21 18 34.77 +62 35 08.1", 2.45, "A8Vn", 49.),
+    ("Navi", "Cas", "00 56 42.53 +60 43 00.3", 2.47, "B0.5IVe", 550.),
+    ("Markab", "Peg", "23 04 45.65 +15 12 19.3", 2.48, "A0III-IV", 133.),
+    ("Aljanah", "Cyg", "20 46 12.68 +33 58 12.9", 2.48, "K0III", 72.),
+    ("Menkar", "Cet", "03 02 16.77 +04 05 23.1", 2.54, "M1.5IIIa", 249.),
+    ("Zosma", "Leo", "11 14 06.50 +20 31 25.4", 2.56, "A4V", 58.),
+    ("Arneb", "Lep", "05 32 43.82 -17 49 20.2", 2.58, "F0Ib", 2200.),
+    ("Gienah", "Crv", "12 15 48.37 -17 32 30.9", 2.59, "B8III", 154.),
+    ("Ascella", "Sgr", "19 02 36.73 -29 52 48.4", 2.60, "A2.5V", 88.),
+    ("Zubeneschamali", "Lib", "15 17 00.41 -09 22 58.5", 2.61, "B8V", 185.),
+    ("Unukalhai", "Ser", "15 44 16.07 +06 25 32.3", 2.63, "K2IIIb", 74.),
+    ("Ruchbah", "Cas", "01 25 48.95 +60 14 07.0", 2.68, "A5III-IV", 99.),
+    ("Tarazed", "Aql", "19 46 15.58 +10 36 47.7", 2.72, "K3II", 395.),
+    ("Kornephoros", "Her", "16 30 13.20 +21 29 22.6", 2.78, "G7IIIa", 139.),
+    ("Vindemiatrix", "Vir", "13 02 10.60 +10 57 32.9", 2.79, "G8III", 109.),
+    ("Algenib", "Peg", "00 13 14.15 +15 11 00.9", 2.83, "B2IV", 390.),
+    ("Alcyone", "Tau", "03 47 29.08 +24 06 18.5", 2.87, "B7IIIe", 440.),
+    ("Acamar", "Eri", "02 58 15.68 -40 18 17.0", 2.88, "A3IV-V", 161.),
+    ("Alnasl", "Sgr", "18 05 48.49 -30 25 26.7", 2.98, "K0III", 97.),
+    ("Albireo", "Cyg", "19 30 43.28 +27 57 34.8", 3.05, "K3II", 430.),
+    ("Rasalgethi", "Her", "17 14 38.86 +14 23 25.2", 3.06, "M5Ib-II", 360.),
+    ("Thuban", "Dra", "14 04 23.35 +64 22 33.1", 3.65, "A0III", 303.),
+    ("Tiaki", "Gru", "22 42 40.05 -46 53 04.5", 2.07, "M5III", 177.),
+    ("Muhlifain", "Cen", "12 41 31.04 -48 57 35.5", 2.17, "A1IV", 130.),
+    ("Larawag", "Sco", "16 50 09.81 -34 17 35.6", 2.29, "K1III", 65.),
+    ("Epsilon Centauri", "Cen", "13 39 53.26 -53 27 59.0", 2.30, "B1III", 380.),
+    ("Aludra", "CMa", "07 24 05.70 -29 18 11.2", 2.45, "B5Ia", 2000.),
+    ("Markeb", "Vel", "09 22 06.82 -55 00 38.4", 2.47, "B2IV", 570.),
+    ("Delta Centauri", "Cen", "12 08 21.50 -50 43 20.7", 2.52, "B2Vne", 400.),
+    ("Zeta Centauri", "Cen", "13 55 32.39 -47 17 18.2", 2.55, "B2.5IV", 380.),
+    ("Zeta Ophiuchi", "Oph", "16 37 09.54 -10 34 01.5", 2.56, "O9.5V", 370.),
+    ("Mahasim", "Aur", "05 59 43.27 +37 12 45.3", 2.62, "A0pSi", 166.),
+    ("Acrab", "Sco", "16 05 26.23 -19 48 19.6", 2.62, "B1V", 400.),
+    ("Sheratan", "Ari", "01 54 38.41 +20 48 28.9", 2.64, "A5V", 59.6),
+    ("Phact", "Col", "05 39 38.94 -34 04 26.8", 2.65, "B9Ve", 261.),
+    ("Kraz", "Crv", "12 34 23.23 -23 23 48.3", 2.65, "G5II", 140.),
+    ("Muphrid", "Boo", "13 54 41.08 +18 23 51.8", 2.68, "G0IV", 37.),
+    ("Beta Lupi", "Lup", "14 58 31.93 -43 08 02.3", 2.68, "B2IV", 383.),
+    ("Hassaleh", "Aur", "04 56 59.62 +33 09 57.9", 2.69, "K3II", 490.),
+    ("Alpha Muscae", "Mus", "12 37 11.02 -69 08 08.0", 2.69, "B2IV-V", 315.),
+    ("Mu Velorum", "Vel", "10 46 46.18 -49 25 12.9", 2.69, "G6III", 116.),
+    ("Ahadi", "Pup", "07 17 08.56 -37 05 51.0", 2.70, "K3Ib", 1100.),
+    ("Kaus Media", "Sgr", "18 20 59.64 -29 49 41.2", 2.70, "K3IIIa", 350.),
+    ("Lesath", "Sco", "17 30 45.84 -37 17 44.9", 2.70, "B2IV", 580.),
+    ("Athebyne", "Dra", "16 23 59.49 +61 30 51.2", 2.73, "G8III", 88.),
+    ("Porrima", "Vir", "12 41 39.64 -01 26 57.7", 2.74, "F0V", 38.),
+    ("Iota Centauri", "Cen", "13 20 35.82 -36 42 44.3", 2.75, "A2V", 59.),
+    ("Zubenelgenubi", "Lib", "14 50 52.71 -16 02 30.4", 2.75, "A3IV", 75.),
+    ("Yed Prior", "Oph", "16 14 20.74 -03 41 39.6", 2.75, "M0.5III", 171.),
+    ("Theta Carinae", "Car", "10 42 57.40 -64 23 40.0", 2.76, "B0.5Vp", 460.),
+    ("Cebalrai", "Oph", "17 43 28.35 +04 34 02.3", 2.76, "K2III", 82.),
+    ("Hatysa", "Ori", "05 35 25.98 -05 54 35.6", 2.77, "O9III", 1300.),
+    ("Gamma Lupi", "Lup", "15 35 08.45 -41 10 00.3", 2.78, "B2IV", 420.),
+    ("Imai", "Cru", "12 15 08.72 -58 44 56.1", 2.79, "B2IV", 345.),
+    ("Rastaban", "Dra", "17 30 25.96 +52 18 05.0", 2.79, "G2Ib-IIa", 380.),
+    ("Cursa", "Eri", "05 07 50.99 -05 05 11.2", 2.79, "A3IV", 89.),
+    ("Beta Hydri", "Hyi", "00 25 45.07 -77 15 15.3", 2.80, "G2IV", 24.3),
+    ("Deneb Algedi", "Cap", "21 47 02.44 -16 07 38.2", 2.81, "A5mF2", 39.),
+    ("Zeta Herculis", "Her", "16 41 17.16 +31 36 09.8", 2.81, "G0IV", 35.),
+    ("Tureis", "Pup", "08 07 32.65 -24 18 15.6", 2.81, "F5II", 63.),
+    ("Kaus Borealis", "Sgr", "18 27 58.24 -25 25 18.1", 2.81, "K0III", 78.),
+    ("Paikauhale", "Sco", "16 35 52.95 -28 12 57.7", 2.82, "B0V", 470.),
+    ("Beta Arae", "Ara", "17 25 17.99 -55 31 47.6", 2.84, "K3Ib", 650.),
+    ("Alpha Arae", "Ara", "17 31 50.49 -49 52 34.1", 2.84, "B2Vne", 270.),
+    ("Nihal", "Lep", "05 28 14.72 -20 45 33.9", 2.84, "G5II", 160.),
+    ("Zeta Persei", "Per", "03 54 07.92 +31 53 01.1", 2.85, "B1Ib", 750.),
+    ("Beta Trianguli Australis", "TrA", "15 55 08.56 -63 25 50.6", 2.85, "F1V", 40.),
+    ("Alpha Hydri", "Hyi", "01 58 46.19 -61 34 11.5", 2.86, "F0V", 71.8),
+    ("Alpha Tucanae", "Tuc", "22 18 30.09 -60 15 34.5", 2.86, "K3III", 199.),
+    ("Sadalsuud", "Aqr", "21 31 33.53 -05 34 16.2", 2.87, "G0Ib", 540.),
+    ("Fawaris", "Cyg", "19 44 58.48 +45 07 50.9", 2.87, "B9.5III", 165.),
+    ("Tejat", "Gem", "06 22 57.63 +22 30 48.9", 2.88, "M3III", 230.),
+    ("Epsilon Persei", "Per", "03 57 51.23 +40 00 36.8", 2.88, "B0.5IV", 640.),
+    ("Cor Caroli", "CVn", "12 56 01.67 +38 19 06.2", 2.89, "A0pSiEuHg", 110.),
+    ("Gomeisa", "CMi", "07 27 09.04 +08 17 21.5", 2.89, "B8V", 160.),
+    ("Albaldah", "Sgr", "19 09 45.83 -21 01 25.0", 2.89, "F2II", 510.),
+    ("Fang", "Sco", "15 58 51.11 -26 06 50.8", 2.89, "B1V", 590.),
+    ("Alniyat", "Sco", "16 21 11.32 -25 35 34.1", 2.89, "B1III", 700.),
+    ("Gamma Trianguli Australis", "TrA", "15 18 54.58 -68 40 46.4", 2.89, "A1III", 184.),
+    ("Gamma Persei", "Per", "03 04 47.79 +53 30 23.2", 2.93, "G8III", 256.),
+    ("Tau Puppis", "Pup", "06 49 56.17 -50 36 53.4", 2.93, "K1III", 183.),
+    ("Sadalmelik", "Aqr", "22 05 47.04 -00 19 11.5", 2.94, "G2Ib", 520.),
+    ("Matar", "Peg", "22 43 00.14 +30 13 17.0", 2.94, "G2II-III", 214.),
+    ("Algorab", "Crv", "12 29 51.86 -16 30 55.6", 2.95, "B9.5IV", 87.),
+    ("Zaurak", "Eri", "03 58 01.77 -13 30 30.7", 2.95, "M1IIIb", 203.),
+    ("Upsilon Carinae", "Car", "09 47 06.12 -65 04 19.2", 2.97, "A6II", 1400.),
+    ("Mebsuta", "Gem", "06 43 55.93 +25 07 52.0", 2.98, "G8Ib", 840.),
+    ("Algenubi", "Leo", "09 45 51.07 +23 46 27.3", 2.98, "G1II", 250.),
+    ("Okab", "Aql", "19 05 24.61 +13 51 48.5", 2.99, "A0V", 83.),
+    ("Almaaz", "Aur", "05 01 58.13 +43 49 23.9", 2.99, "A9Ia", 2000.),
+    ("Iota1 Scorpii", "Sco", "17 47 35.08 -40 07 37.2", 2.99, "F2Ia", 1900.),
+    ("Gamma Hydrae", "Hya", "13 18 55.30 -23 10 17.5", 3.00, "G8III", 134.),
+    ("Tianguan", "Tau", "05 37 38.69 +21 08 33.2", 3.00, "B2IIIpe", 440.),
+    ("Beta Trianguli", "Tri", "02 09 32.63 +34 59 14.3", 3.00, "A5III", 127.),
+    ("Pherkad", "UMi", "15 20 43.72 +71 50 02.5", 3.00, "A3II-III", 487.),
+    ("Aldhanab", "Gru", "21 53 55.73 -37 21 53.5", 3.01, "B8IV", 211.),
+    ("Delta Persei", "Per", "03 42 55.50 +47 47 15.2", 3.01, "B5III", 520.),
+    ("Girtab", "Sco", "17 42 29.28 -39 01 47.9", 3.01, "B1.5III", 460.),
+    ("Psi Ursae Majoris", "UMa", "11 09 39.81 +44 29 54.6", 3.01, "K1III", 147.),
+    ("Furud", "CMa", "06 20 18.79 -30 03 48.1", 3.02, "B2.5V", 362.),
+    ("Omicron2 Canis Majoris", "CMa", "07 03 01.47 -23 50 00.0", 3.02, "B3Ia", 2800.),
+    ("Minkar", "Crv", "12 10 07.48 -22 37 11.2", 3.02, "K2III", 300.),
+    ("Seginus", "Boo", "14 32 04.67 +38 18 29.7", 3.03, "A7III", 85.),
+    ("Mu Centauri", "Cen", "13 49 36.99 -42 28 25.4", 3.04, "B2Vnpe", 510.),
+    ("Beta Muscae", "Mus", "12 46 16.98 -68 06 29.0", 3.04, "B2V", 340.),
+    ("Xamidimura", "Sco", "16 51 52.23 -38 02 50.6", 3.04, "B1.5V", 500.),
+    ("Dabih", "Cap", "20 21 00.67 -14 46 52.9", 3.05, "K0II", 330.),
+    ("Tania Australis", "UMa", "10 22 19.74 +41 29 58.3", 3.06, "M0III", 230.),
+    ("Altais", "Dra", "19 12 33.30 +67 39 41.5", 3.07, "G9III", 97.),
+    ("Zeta Hydrae", "Hya", "08 55 23.63 +05 56 44.0", 3.11, "G9IIIa", 167.),
+    ("Nu Hydrae", "Hya", "10 49 37.49 -16 11 37.1", 3.11, "K1.5III", 144.),
+    ("Alpha Indi", "Ind", "20 37 34.03 -47 17 29.4", 3.11, "K0III", 98.),
+    ("Eta Sagittarii", "Sgr", "18 17 37.64 -36 45 42.1", 3.11, "M3.5IIIab", 146.),
+    ("Zeta Arae", "Ara", "16 58 37.21 -55 59 24.5", 3.12, "K3III", 490.),
+    ("Wazn", "Col", "05 50 57.59 -35 46 05.9", 3.12, "K1.5III", 87.),
+    ("Kappa Centauri", "Cen", "14 59 09.68 -42 06 15.1", 3.13, "B2IV", 380.),
+    ("Lambda Centauri", "Cen", "11 35 46.88 -63 01 11.4", 3.13, "B9III", 410.),
+    ("N Velorum", "Vel", "09 31 13.32 -57 02 03.8", 3.13, "K5III", 240.),
+    ("Sarin", "Her", "17 15 01.91 +24 50 21.1", 3.14, "A1Vann", 75.),
+    ("Alpha Lyncis", "Lyn", "09 21 03.30 +34 23 33.2", 3.14, "M0III", 203.),
+    ("Talitha", "UMa", "08 59 12.45 +48 02 30.6", 3.14, "A7V", 47.),
+    ("Pi Herculis", "Her", "17 15 02.83 +36 48 32.9", 3.16, "K3II", 377.),
+    ("Haedus", "Aur", "05 06 30.89 +41 14 04.1", 3.17, "B3V", 243.),
+    ("Aldhibah", "Dra", "17 08 47.20 +65 42 52.9", 3.17, "B6III", 330.),
+    ("Nu Puppis", "Pup", "06 37 45.67 -43 11 45.4", 3.17, "B8III", 370.),
+    ("Phi Sagittarii", "Sgr", "18 45 39.39 -26 59 26.8", 3.17, "B8III", 230.),
+    ("Theta Ursae Majoris", "UMa", "09 32 51.43 +51 40 38.3", 3.17, "F6IV", 44.),
+    ("Epsilon Leporis", "Lep", "05 05 27.66 -22 22 15.7", 3.19, "K4III", 213.),
+    ("Kappa Ophiuchi", "Oph", "16 57 40.10 +09 22 30.1", 3.19, "K2III", 88.),
+    ("Tabit", "Ori", "04 49 50.41 +06 57 40.6", 3.19, "F6V", 26.),
+    ("Alpha Circini", "Cir", "14 42 30.42 -64 58 30.5", 3.19, "A7Vp", 54.),
+    ("Errai", "Cep", "23 39 20.85 +77 37 56.2", 3.21, "K1IV", 45.),
+    ("Zeta Cygni", "Cyg", "21 12 56.19 +30 13 37.0", 3.21, "G8II", 143.),
+    ("Fuyue", "Sco", "17 49 51.48 -37 02 35.9", 3.21, "K2III", 127.),
+    ("Delta Lupi", "Lup", "15 21 22.32 -40 38 51.1", 3.22, "B1.5IVn", 880.),
+    ("Alfirk", "Cep", "21 28 39.60 +70 33 38.6", 3.23, "B1III", 690.),
+    ("Theta Aquilae", "Aql", "20 11 18.28 -00 49 17.3", 3.24, "B9.5III", 290.),
+    ("Gamma Hydri", "Hyi", "03 47 14.34 -74 14 20.3", 3.24, "M2III", 214.),
+    ("Sulafat", "Lyr", "18 58 56.62 +32 41 22.4", 3.24, "B9III", 620.),
+    ("Yed Posterior", "Oph", "16 18 19.29 -04 41 33.0", 3.24, "G9.5IIIb", 106.),
+    ("Pi Hydrae", "Hya", "14 06 22.30 -26 40 56.5", 3.25, "K2III", 101.),
+    ("Sigma Puppis", "Pup", "07 29 13.83 -43 18 05.2", 3.25, "K5III", 194.),
+    ("Eta Serpentis", "Ser", "18 21 18.60 -02 53 55.8", 3.26, "K0III", 60.),
+    ("Delta Andromedae", "And", "00 39 19.68 +30 51 39.7", 3.27, "K3III", 101.),
+    ("Skat", "Aqr", "22 54 39.01 -15 49 14.9", 3.27, "A3V", 160.),
+    ("Alpha Doradus", "Dor", "04 33 59.78 -55 02 42.0", 3.27, "A0IIIp", 170.),
+    ("Theta Ophiuchi", "Oph", "17 22 00.58 -24 59 58.4", 3.27, "B2IV", 436.),
+    ("Alpha Pictoris", "Pic", "06 48 11.46 -61 56 29.0", 3.27, "A7IV", 97.),
+    ("Propus", "Gem", "06 14 52.66 +22 30 24.5", 3.28, "M3III", 380.),
+    ("Edasich", "Dra", "15 24 55.77 +58 57 57.8", 3.29, "K2III", 101.),
+    ("Mu Leporis", "Lep", "05 12 55.90 -16 12 19.7", 3.29, "B9IVmnp", 186.),
+    ("Brachium", "Lib", "15 04 04.22 -25 16 55.1", 3.29, "M3.5III", 290.),
+    ("p Carinae", "Car", "10 32 01.46 -61 41 07.2", 3.30, "B4Vne", 490.),
+    ("Beta Phoenicis", "Phe", "01 06 05.04 -46 43 06.3", 3.31, "G8III", 198.),
+    ("Megrez", "UMa", "12 15 25.56 +57 01 57.4", 3.31, "A3V", 58.),
+    ("Omega Carinae", "Car", "10 13 44.22 -70 02 16.5", 3.32, "B8IIIe", 340.),
+    ("Tau Sagittarii", "Sgr", "19 06 56.41 -27 40 13.5", 3.32, "K1.5III", 120.),
+    ("Nu Ophiuchi", "Oph", "17 59 01.59 -09 46 25.1", 3.33, "K0III", 153.),
+    ("Meissa", "Ori", "05 35 08.28 +09 56 03.0", 3.33, "O8III", 1100.),
+    ("Alpha Reticuli", "Ret", "04 14 25.48 -62 28 25.9", 3.33, "G8II-III", 160.),
+    ("Eta Scorpii", "Sco", "17 12 09.20 -43 14 21.1", 3.33, "F2V", 72.),
+    ("Gamma Arae", "Ara", "17 25 23.66 -56 22 39.8", 3.34, "B1Ib", 1100.),
+    ("Chertan", "Leo", "11 14 14.41 +15 25 46.5", 3.34, "A2IV", 165.),
+    ("Azmidi", "Pup", "07 49 17.66 -24 51 35.2", 3.34, "G6Ia", 1200.),
+    ("Zeta Cephei", "Cep", "22 10 51.28 +58 12 04.5", 3.35, "K1.5Ib", 830.),
+    ("Eta Orionis", "Ori", "05 24 28.62 -02 23 49.7", 3.35, "B0.5V", 900.),
+    ("Delta Aquilae", "Aql", "19 25 29.90 +03 06 53.2", 3.36, "F2IV", 50.),
+    ("Alzirr", "Gem", "06 45 17.36 +12 53 44.1", 3.36, "F5IV", 57.),
+    ("Muscida", "UMa", "08 30 15.87 +60 43 05.4", 3.36, "G5III", 179.),
+    ("Segin", "Cas", "01 54 23.73 +63 40 12.4", 3.37, "B3III", 410.),
+    ("Epsilon Lupi", "Lup", "15 22 40.87 -44 41 22.6", 3.37, "B2IV-V", 500.),
+    ("Heze", "Vir", "13 34 41.59 -00 35 44.9", 3.37, "A3V", 74.),
+    ("Ashlesha", "Hya", "08 46 46.51 +06 25 07.7", 3.38, "G5III", 130.),
+    ("Minelauva", "Vir", "12 55 36.21 +03 23 50.9", 3.38, "M3III", 202.),
+    ("Gorgonea Tertia", "Per", "03 05 10.59 +38 50 25.0", 3.39, "M4II", 310.),
+    ("Chamukuy", "Tau", "04 28 39.74 +15 52 15.2", 3.40, "A7III", 150.),
+    ("Nu Centauri", "Cen", "13 49 30.28 -41 41 15.8", 3.41, "B2IV", 440.),
+    ("Eta Cephei", "Cep", "20 45 17.38 +61 50 19.6", 3.41, "K0IV", 47.),
+    ("Zeta Lupi", "Lup", "15 12 17.10 -52 05 57.3", 3.41, "G8III", 116.),
+    ("Homam", "Peg", "22 41 27.72 +10 49 52.9", 3.41, "B8V", 204.),
+    ("Gamma Phoenicis", "Phe", "01 28 21.93 -43 19 05.6", 3.41, "M0IIIa", 235.),
+    ("Mothallah", "Tri", "01 53 04.91 +29 34 43.8", 3.41, "F6IV", 63.),
+    ("Mu Herculis", "Her", "17 46 27.53 +27 43 14.4", 3.42, "G5IV", 27.),
+    ("Eta Lupi", "Lup", "16 00 07.33 -38 23 48.2", 3.42, "B2.5IVn", 440.),
+    ("Beta Pavonis", "Pav", "20 44 57.49 -66 12 11.6", 3.42, "A6IV", 137.),
+    ("Lambda Aquilae", "Aql", "19 06 14.94 -04 52 57.2", 3.43, "A0V", 125.),
+    ("a Carinae", "Car", "09 10 58.09 -58 58 00.8", 3.43, "B2IV", 450.),
+    ("Achird", "Cas", "00 49 06.29 +57 48 54.7", 3.44, "F9V", 19.4),
+    ("Adhafera", "Leo", "10 16 41.42 +23 25 02.3", 3.44, "F0III", 260.),
+    ("Eta Ceti", "Cet", "01 08 35.39 -10 10 56.2", 3.45, "K2III", 124.),
+    ("Tania Borealis", "UMa", "10 17 05.79 +42 54 51.7", 3.45, "A2IV", 138.),
+    ("Chi Carinae", "Car", "07 56 46.71 -52 58 56.5", 3.46, "B3IVp", 460.),
+    ("Delta Bootis", "Boo", "15 15 30.16 +33 18 53.4", 3.47, "G8III", 120.),
+    ("Sigma Canis Majoris", "CMa", "07 01 43.15 -27 56 05.4", 3.47, "K7Ib", 1100.),
+    ("Kaffaljidhma", "Cet", "02 43 18.04 +03 14 08.9", 3.47, "A3V", 82.),
+    ("Gamma Sagittae", "Sge", "19 58 45.43 +19 29 31.7", 3.47, "M0III", 258.),
+    ("Lambda Tauri", "Tau", "04 00 40.82 +12 29 25.2", 3.47, "B3V", 480.),
+    ("Eta Herculis", "Her", "16 42 53.77 +38 55 20.1", 3.48, "G7III", 109.),
+    ("Eta Leonis", "Leo", "10 07 19.95 +16 45 45.6", 3.48, "A0Ib", 1270.),
+    ("Sadalbari", "Peg", "22 50 00.19 +24 36 05.7", 3.48, "K0III", 106.),
+    ("Nekkar", "Boo", "15 01 56.76 +40 23 26.0", 3.49, "G8III", 225.),
+    ("Epsilon Gruis", "Gru", "22 48 33.30 -51 19 00.7", 3.49, "A2V", 130.),
+    ("Alpha Telescopii", "Tel", "18 26 58.42 -45 58 06.5", 3.49, "B3IV", 278.),
+    ("Alula Borealis", "UMa", "11 18 28.74 +33 05 39.5", 3.49, "K3III", 400.),
+    ("Tarf", "Cnc", "08 16 30.92 +09 11 08.0", 3.50, "K4III", 290.),
+    ("Tau Ceti", "Cet", "01 44 04.08 -15 56 14.9", 3.50, "G8V", 11.9),
+    ("Xi2 Sagittarii", "Sgr", "18 57 43.80 -21 06 23.9", 3.51, "K1III", 370.),
+    ("Subra", "Leo", "09 41 09.03 +09 53 32.3", 3.52, "F5II", 135.),
+    ("Sheliak", "Lyr", "18 50 04.80 +33 21 45.6", 3.52, "B7Vpe", 960.),
+    ("Wasat", "Gem", "07 20 07.38 +21 58 56.4", 3.53, "F0IV", 60.),
+    ("Biham", "Peg", "22 10 11.99 +06 11 52.3", 3.53, "A2V", 92.),
+    ("Ain", "Tau", "04 28 36.99 +19 10 49.6", 3.53, "K0III", 147.),
+    ("Rana", "Eri", "03 43 14.90 -09 45 48.2", 3.54, "K0IV", 29.5),
+    ("Xi Hydrae", "Hya", "11 33 00.12 -31 51 27.4", 3.54, "G7III", 130.),
+    ("Mu Serpentis", "Ser", "15 49 37.21 -03 25 48.7", 3.54, "A0V", 170.),
+    ("Xi Serpentis", "Ser", "17 37 35.20 -15 23 54.8", 3.54, "F0IIIp", 105.),
+    ("Phi Velorum", "Vel", "09 56 51.74 -54 34 04.0", 3.54, "B5Ib", 1600.),
+    ("Chi Draconis", "Dra", "18 21 03.38 +72 43 58.3", 3.55, "F7V", 26.),
+    ("Zeta Leporis", "Lep", "05 46 57.34 -14 49 19.0", 3.55, "A2Vann", 70.),
+    ("Iota Lupi", "Lup", "14 19 24.22 -46 03 28.4", 3.55, "B2.5IVn", 345.),
+    ("Iota Ceti", "Cet", "00 19 25.67 -08 49 26.1", 3.56, "K1.5III", 270.),
+    ("Delta Crateris", "Crt", "11 19 20.45 -14 46 42.7", 3.56, "G9III", 163.),
+    ("Phi Eridani", "Eri", "02 16 30.59 -51 30 43.8", 3.56, "B8IV", 154.),
+    ("Phi1 Lupi", "Lup", "15 21 48.37 -36 15 40.9", 3.56, "K4III", 290.),
+    ("Delta Pavonis", "Pav", "20 08 43.61 -66 10 55.4", 3.56, "G8IV", 19.9),
+    ("Algedi", "Cap", "20 18 03.26 -12 32 41.5", 3.57, "G9III", 109.),
+    ("Kappa Geminorum", "Gem", "07 44 26.85 +24 23 52.8", 3.57, "G8III", 140.),
+    ("Rho Bootis", "Boo", "14 31 49.79 +30 22 17.2", 3.58, "K3III", 150.),
+    ("Upsilon Librae", "Lib", "15 37 01.45 -28 08 06.3", 3.58, "K3III", 195.),
+    ("Ginan", "Cru", "12 21 21.61 -60 24 04.1", 3.59, "K3III", 230.),
+    ("Gamma Leporis", "Lep", "05 44 27.79 -22 26 54.2", 3.59, "F6V", 29.),
+    ("Tau Orionis", "Ori", "05 17 36.39 -06 50 39.9", 3.59, "B5III", 555.),
+    ("Omicron Tauri", "Tau", "03 24 48.79 +09 01 43.9", 3.60, "G8III", 212.),
+    ("Omicron Velorum", "Vel", "08 40 17.59 -52 55 18.8", 3.60, "B3III-IV", 490.),
+    ("Psi Velorum", "Vel", "09 30 42.00 -40 28 00.3", 3.60, "F0V", 61.)
+];
+
+pub fn bright_stars() -> Vec<Star> {
+    BRIGHT_STARS.iter().enumerate().filter_map(|(id, &(name, constellation, radec, v, class, distance))| {
+        let coord = parse_sexagesimal(radec).ok()?;
+        Some(Star {
+            id,
+            pos: StellarPosition::new(distance, coord.right_ascension, coord.declination).into(),
+            name: name.to_string(),
+            class: class.to_string(),
+            constellation: constellation.parse().ok(),
+            photometry: Photometry::default().with(Band::V, Some(v)),
+            provenance: vec![Provenance::new(Source::Catalog("bright_stars".to_string()), Some(id as u64))],
+            ..Default::default()
+        })
+    }).collect()
+}
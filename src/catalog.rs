@@ -0,0 +1,530 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::ops::Add;
+use std::path::Path;
+use crate::{binary, designation, healpix, identifier, import, Band, CellKey, Constellation, Star, StarKey, StellarPosition};
+use crate::designation::Designation;
+use crate::synthetic::Rng;
+
+// Pixels of about 25 arcsec; fine enough that ordering within a pixel doesn't matter.
+const SPATIAL_NSIDE: u32 = 1 << 13;
+
+// How `dedup`, `Extend` and `+` treat stars that are already in the catalog.
+#[cfg_attr(feature = "schemars", derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema))]
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub enum DedupPolicy {
+    KeepAll,
+    // Same `StarKey`, i.e. the same primary identifier.
+    #[default]
+    ByKey,
+    // Within `tolerance_arcsec` of an earlier star, for sources that don't share identifiers. When
+    // both stars carry error ellipses they have to agree within `MATCH_SIGMA` instead.
+    ByPosition { tolerance_arcsec: f32 }
+}
+
+#[cfg_attr(feature = "schemars", derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema))]
+#[derive(Default, Clone, Debug)]
+pub struct Catalog {
+    pub stars: Vec<Star>,
+    pub epoch: Option<f64>,
+    pub dedup_policy: DedupPolicy
+}
+
+impl Catalog {
+    pub fn new(stars: Vec<Star>) -> Self {
+        let epoch = common_epoch(&stars);
+        Self { stars, epoch, dedup_policy: DedupPolicy::default() }
+    }
+
+    pub fn with_dedup_policy(mut self, policy: DedupPolicy) -> Self {
+        self.dedup_policy = policy;
+        self
+    }
+
+    pub fn normalize_to_epoch(&mut self, jd: f64) {
+        for star in &mut self.stars {
+            star.normalize_to_epoch(jd);
+        }
+        self.epoch = Some(jd);
+    }
+
+    // The sky `years` from now (negative for the past) with stars moved along their proper
+    // motion at fixed distance. Good for a few thousand years; see `sky_at_3d` beyond that.
+    pub fn sky_at(&self, years: f64) -> Catalog {
+        let mut catalog = self.clone();
+        for star in &mut catalog.stars {
+            star.normalize_to_epoch(star.epoch + years * 365.25);
+        }
+        catalog.epoch = catalog.epoch.map(|epoch| epoch + years * 365.25);
+        catalog
+    }
+
+    // Like `sky_at`, but moving stars through space so that distances, proper motions and
+    // magnitudes change too.
+    pub fn sky_at_3d(&self, years: f64) -> Catalog {
+        let mut catalog = self.clone();
+        for star in &mut catalog.stars {
+            star.propagate(years);
+        }
+        catalog.epoch = catalog.epoch.map(|epoch| epoch + years * 365.25);
+        catalog
+    }
+
+    pub fn import<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self::new(import(path)?))
+    }
+
+    pub fn save_binary<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn std::error::Error>> {
+        let mut w = BufWriter::new(File::create(path)?);
+        binary::write_header(&mut w, self.stars.len())?;
+        for star in &self.stars {
+            binary::write_star(&mut w, star)?;
+        }
+        w.flush()?;
+        Ok(())
+    }
+
+    pub fn load_binary<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut r = BufReader::new(File::open(path)?);
+        let count = binary::read_header(&mut r)?;
+        // The count comes from the file, so it only bounds the loop; stars are pushed as they
+        // actually read, and a truncated file fails after what it really holds.
+        let mut stars = vec![];
+        for _ in 0..count {
+            stars.push(binary::read_star(&mut r)?);
+        }
+        Ok(Self::new(stars))
+    }
+
+    pub fn len(&self) -> usize {
+        self.stars.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stars.is_empty()
+    }
+
+    // Uniform sample of `n` stars without replacement (all of them if `n >= len`), in random order.
+    pub fn sample(&self, n: usize, rng: &mut Rng) -> Catalog {
+        let mut indices = (0..self.stars.len()).collect::<Vec<_>>();
+        let n = n.min(indices.len());
+        for i in 0..n {
+            let j = i + rng.below(indices.len() - i);
+            indices.swap(i, j);
+        }
+        Catalog::new(indices[..n].iter().map(|&i| self.stars[i].clone()).collect())
+    }
+
+    // Designation in `catalog` -> index into `stars`, for joining with external archives.
+    pub fn index_by_catalog(&self, catalog: &str) -> HashMap<&str, usize> {
+        self.stars.iter().enumerate()
+            .filter_map(|(index, star)| Some((star.id_in_catalog(catalog)?, index)))
+            .collect()
+    }
+
+    pub fn find_by_key(&self, key: &StarKey) -> Option<&Star> {
+        self.stars.iter().find(|star| star.key().as_ref() == Some(key))
+    }
+
+    // Drops every star that duplicates an earlier one under `dedup_policy`; the first one keeps
+    // the provenance of the dropped duplicates.
+    pub fn dedup(&mut self) {
+        let mut by_key: HashMap<StarKey, usize> = HashMap::new();
+        let mut by_cell: HashMap<CellKey, Vec<usize>> = HashMap::new();
+        let mut stars: Vec<Star> = Vec::with_capacity(self.stars.len());
+        for star in std::mem::take(&mut self.stars) {
+            let duplicate = match self.dedup_policy {
+                DedupPolicy::KeepAll => None,
+                DedupPolicy::ByKey => star.key().and_then(|key| by_key.get(&key).copied()),
+                DedupPolicy::ByPosition { tolerance_arcsec } => {
+                    let coord = StellarPosition::from(star.pos).coord;
+                    coord.cell_keys_near(tolerance_arcsec).iter()
+                        .filter_map(|cell| by_cell.get(cell))
+                        .flatten()
+                        .copied()
+                        .find(|&i| stars[i].matches_position(&star, tolerance_arcsec))
+                }
+            };
+            if let Some(index) = duplicate {
+                stars[index].provenance.extend(star.provenance);
+                continue;
+            }
+            match self.dedup_policy {
+                DedupPolicy::KeepAll => {}
+                DedupPolicy::ByKey => {
+                    if let Some(key) = star.key() {
+                        by_key.insert(key, stars.len());
+                    }
+                }
+                DedupPolicy::ByPosition { tolerance_arcsec } => {
+                    by_cell.entry(StellarPosition::from(star.pos).coord.cell_key(tolerance_arcsec)).or_default().push(stars.len());
+                }
+            }
+            stars.push(star);
+        }
+        self.stars = stars;
+    }
+
+    // Sets every `Star::id` to its position in the catalog.
+    pub fn reindex(&mut self) {
+        for (id, star) in self.stars.iter_mut().enumerate() {
+            star.id = id;
+        }
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Star> {
+        self.stars.iter()
+    }
+
+    // Batches of up to `target_chunk_size` stars in NESTED HEALPix order, so each batch covers a
+    // compact patch of sky. Ordering ignores distance.
+    pub fn chunks_spatial(&self, target_chunk_size: usize) -> impl Iterator<Item = Vec<&Star>> + '_ {
+        let mut keyed = self.stars.iter()
+            .map(|star| (healpix::ang2pix_nest(SPATIAL_NSIDE, &StellarPosition::from(star.pos).coord), star))
+            .collect::<Vec<_>>();
+        keyed.sort_by_key(|(pixel, _)| *pixel);
+        let chunk_size = target_chunk_size.max(1);
+        let mut stars = keyed.into_iter().map(|(_, star)| star).peekable();
+        std::iter::from_fn(move || {
+            stars.peek()?;
+            Some(stars.by_ref().take(chunk_size).collect())
+        })
+    }
+
+    pub fn stats(&self) -> CatalogStats {
+        let positions = self.stars.iter().map(|star| StellarPosition::from(star.pos)).collect::<Vec<_>>();
+        let mut per_class = BTreeMap::new();
+        let mut per_constellation = BTreeMap::new();
+        for star in &self.stars {
+            *per_class.entry(spectral_class_key(star)).or_insert(0) += 1;
+            *per_constellation.entry(star.constellation).or_insert(0) += 1;
+        }
+        CatalogStats {
+            count: self.stars.len(),
+            distance: Summary::from_values(positions.iter().map(|p| p.distance).filter(|d| *d > 0.)),
+            mag_u: Summary::from_values(self.stars.iter().filter_map(|s| s.photometry.get(Band::U))),
+            mag_b: Summary::from_values(self.stars.iter().filter_map(|s| s.photometry.get(Band::B))),
+            mag_v: Summary::from_values(self.stars.iter().filter_map(|s| s.photometry.get(Band::V))),
+            mag_r: Summary::from_values(self.stars.iter().filter_map(|s| s.photometry.get(Band::R))),
+            mag_i: Summary::from_values(self.stars.iter().filter_map(|s| s.photometry.get(Band::I))),
+            mag_g: Summary::from_values(self.stars.iter().filter_map(|s| s.photometry.get(Band::G))),
+            mag_j: Summary::from_values(self.stars.iter().filter_map(|s| s.photometry.get(Band::J))),
+            mag_h: Summary::from_values(self.stars.iter().filter_map(|s| s.photometry.get(Band::H))),
+            mag_k: Summary::from_values(self.stars.iter().filter_map(|s| s.photometry.get(Band::K))),
+            right_ascension: Summary::from_values(positions.iter().map(|p| p.coord.right_ascension)),
+            declination: Summary::from_values(positions.iter().map(|p| p.coord.declination)),
+            per_class,
+            per_constellation,
+        }
+    }
+
+    // Ranked candidates for a possibly misspelled name: exact matches first, then prefix matches,
+    // then names within an edit distance of about a third of the query length. Proper names,
+    // identifiers and aliases are all searched, ignoring case and whitespace.
+    pub fn find_by_name(&self, query: &str) -> Vec<NameMatch<'_>> {
+        let query = identifier::fold(query);
+        if query.is_empty() {
+            return vec![];
+        }
+        let max_distance = (query.len() / 3).max(1);
+        let mut matches = self.stars.iter().filter_map(|star| {
+            let names = [star.name.as_str(), star.identifier.as_str()].into_iter()
+                .chain(star.aliases.iter().map(|alias| alias.as_str()))
+                .filter(|name| !name.is_empty());
+            names.filter_map(|name| {
+                let folded = identifier::fold(name);
+                let kind = if folded == query {
+                    MatchKind::Exact
+                } else if folded.starts_with(&query) {
+                    MatchKind::Prefix
+                } else {
+                    MatchKind::Fuzzy
+                };
+                let distance = identifier::levenshtein(&query, &folded);
+                (kind != MatchKind::Fuzzy || distance <= max_distance).then_some(NameMatch { star, name, kind, distance })
+            }).min_by_key(|m| (m.kind, m.distance))
+        }).collect::<Vec<_>>();
+        matches.sort_by_key(|m| (m.kind, m.distance));
+        matches
+    }
+
+    pub fn group_by_constellation(&self) -> BTreeMap<Option<Constellation>, Vec<&Star>> {
+        let mut groups: BTreeMap<Option<Constellation>, Vec<&Star>> = BTreeMap::new();
+        for star in &self.stars {
+            groups.entry(star.constellation).or_default().push(star);
+        }
+        groups
+    }
+
+    // Keyed by the class letter ("G", "M", ...), "?" for unparseable spectral types.
+    pub fn group_by_spectral_class(&self) -> BTreeMap<String, Vec<&Star>> {
+        let mut groups: BTreeMap<String, Vec<&Star>> = BTreeMap::new();
+        for star in &self.stars {
+            groups.entry(spectral_class_key(star)).or_default().push(star);
+        }
+        groups
+    }
+
+    // Greek-letter designated stars of a constellation, brightest first. Components ("* alf Cen A")
+    // count under their system's designation.
+    pub fn bayer_stars(&self, constellation: Constellation) -> Vec<&Star> {
+        let mut stars = self.stars.iter().filter_map(|star| match designation::parse_designation(identifier::split_component(&star.identifier).0)? {
+            Designation::Bayer { letter, index, constellation: c } if c == constellation => Some((star, letter, index)),
+            _ => None
+        }).collect::<Vec<_>>();
+        stars.sort_by(|a, b| compare_missing_last(a.0.photometry.get(Band::V), b.0.photometry.get(Band::V)).then((a.1, a.2).cmp(&(b.1, b.2))));
+        stars.into_iter().map(|(star, _, _)| star).collect()
+    }
+
+    // One star per region, e.g. for labeling a sky chart, in constellation or pixel order. Stars
+    // without a constellation are left out of the per-constellation selection.
+    pub fn representatives(&self, strategy: RepresentativeStrategy) -> Vec<&Star> {
+        let groups: Vec<Vec<&Star>> = match strategy.region {
+            Region::Constellation => self.group_by_constellation().into_iter()
+                .filter_map(|(constellation, stars)| constellation.map(|_| stars))
+                .collect(),
+            Region::Healpix { nside } => {
+                let mut cells: BTreeMap<u64, Vec<&Star>> = BTreeMap::new();
+                for star in &self.stars {
+                    cells.entry(healpix::ang2pix_nest(nside, &StellarPosition::from(star.pos).coord)).or_default().push(star);
+                }
+                cells.into_values().collect()
+            }
+        };
+        groups.into_iter().filter_map(|stars| strategy.pick.select(stars)).collect()
+    }
+
+    // Stable sort, earlier keys take precedence. Missing values sort last.
+    pub fn sort_by_keys(&mut self, keys: &[SortKey]) {
+        self.stars.sort_by(|a, b| compare_by_keys(a, b, keys));
+    }
+
+    pub fn sorted_by_keys(&self, keys: &[SortKey]) -> Vec<&Star> {
+        let mut stars = self.stars.iter().collect::<Vec<_>>();
+        stars.sort_by(|a, b| compare_by_keys(a, b, keys));
+        stars
+    }
+}
+
+fn common_epoch(stars: &[Star]) -> Option<f64> {
+    stars.first().map(|s| s.epoch).filter(|epoch| stars.iter().all(|s| s.epoch == *epoch))
+}
+
+// Appends, then drops duplicates under `dedup_policy` and renumbers ids.
+impl Extend<Star> for Catalog {
+    fn extend<I: IntoIterator<Item = Star>>(&mut self, iter: I) {
+        self.stars.extend(iter);
+        self.dedup();
+        self.reindex();
+        self.epoch = common_epoch(&self.stars);
+    }
+}
+
+// Uses the left-hand catalog's dedup policy.
+impl Add for Catalog {
+    type Output = Catalog;
+
+    fn add(mut self, other: Catalog) -> Catalog {
+        self.extend(other.stars);
+        self
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Region {
+    Constellation,
+    Healpix { nside: u32 }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Pick {
+    // Lowest V magnitude.
+    Brightest,
+    // Smallest known distance.
+    Nearest,
+    // The star with the median V magnitude of the region.
+    Median
+}
+
+impl Pick {
+    // Stars missing the deciding value are only picked if no star in the region has it.
+    fn select(self, mut stars: Vec<&Star>) -> Option<&Star> {
+        let distance = |star: &Star| Some(star.distance()).filter(|d| *d > 0.);
+        match self {
+            Self::Brightest => stars.into_iter().min_by(|a, b| compare_missing_last(a.photometry.get(Band::V), b.photometry.get(Band::V))),
+            Self::Nearest => stars.into_iter().min_by(|a, b| compare_missing_last(distance(a), distance(b))),
+            Self::Median => {
+                stars.sort_by(|a, b| compare_missing_last(a.photometry.get(Band::V), b.photometry.get(Band::V)));
+                let known = stars.iter().filter(|star| star.photometry.get(Band::V).is_some()).count();
+                stars.get(known.saturating_sub(1) / 2).copied()
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RepresentativeStrategy {
+    pub region: Region,
+    pub pick: Pick
+}
+
+impl RepresentativeStrategy {
+    pub fn new(region: Region, pick: Pick) -> Self {
+        Self { region, pick }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchKind {
+    Exact,
+    Prefix,
+    Fuzzy
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct NameMatch<'a> {
+    pub star: &'a Star,
+    // The name, identifier or alias that matched.
+    pub name: &'a str,
+    pub kind: MatchKind,
+    // Edit distance between the query and `name`.
+    pub distance: usize
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    Id,
+    Name,
+    Constellation,
+    SpectralClass,
+    Distance,
+    Magnitude
+}
+
+impl SortKey {
+    pub fn compare(&self, a: &Star, b: &Star) -> Ordering {
+        match self {
+            Self::Id => a.id.cmp(&b.id),
+            Self::Name => a.name.cmp(&b.name),
+            Self::Constellation => match (a.constellation, b.constellation) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (a, b) => a.is_none().cmp(&b.is_none())
+            },
+            Self::SpectralClass => compare_missing_last(a.spectral_type().map(|t| t.sequence_index()), b.spectral_type().map(|t| t.sequence_index())),
+            Self::Distance => a.distance().total_cmp(&b.distance()),
+            Self::Magnitude => compare_missing_last(a.photometry.get(Band::V), b.photometry.get(Band::V))
+        }
+    }
+}
+
+fn compare_missing_last(a: Option<f32>, b: Option<f32>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal
+    }
+}
+
+fn compare_by_keys(a: &Star, b: &Star, keys: &[SortKey]) -> Ordering {
+    keys.iter().fold(Ordering::Equal, |ordering, key| ordering.then_with(|| key.compare(a, b)))
+}
+
+fn spectral_class_key(star: &Star) -> String {
+    star.spectral_type().map(|t| t.class.to_string()).unwrap_or_else(|| "?".to_string())
+}
+
+impl From<Vec<Star>> for Catalog {
+    fn from(stars: Vec<Star>) -> Self {
+        Self::new(stars)
+    }
+}
+
+impl<'a> IntoIterator for &'a Catalog {
+    type Item = &'a Star;
+    type IntoIter = std::slice::Iter<'a, Star>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.stars.iter()
+    }
+}
+
+#[derive(Default, Copy, Clone, Debug)]
+pub struct Summary {
+    pub min: f32,
+    pub max: f32,
+    pub mean: f32
+}
+
+impl Summary {
+    pub fn from_values<I: IntoIterator<Item = f32>>(values: I) -> Option<Self> {
+        let mut count = 0;
+        let mut summary = Self { min: f32::INFINITY, max: f32::NEG_INFINITY, mean: 0. };
+        for value in values.into_iter().filter(|v| v.is_finite()) {
+            summary.min = summary.min.min(value);
+            summary.max = summary.max.max(value);
+            summary.mean += value;
+            count += 1;
+        }
+        if count == 0 { return None; }
+        summary.mean /= count as f32;
+        Some(summary)
+    }
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct CatalogStats {
+    pub count: usize,
+    pub distance: Option<Summary>,
+    pub mag_u: Option<Summary>,
+    pub mag_b: Option<Summary>,
+    pub mag_v: Option<Summary>,
+    pub mag_r: Option<Summary>,
+    pub mag_i: Option<Summary>,
+    pub mag_g: Option<Summary>,
+    pub mag_j: Option<Summary>,
+    pub mag_h: Option<Summary>,
+    pub mag_k: Option<Summary>,
+    pub right_ascension: Option<Summary>,
+    pub declination: Option<Summary>,
+    pub per_class: BTreeMap<String, usize>,
+    pub per_constellation: BTreeMap<Option<Constellation>, usize>
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::SimbadCsv;
+
+    #[test]
+    fn binary_round_trips() {
+        let path = SimbadCsv::new(crate::tests::sample_records()).write_temp("binary-source.csv").unwrap();
+        let catalog = Catalog::new(crate::import(&path).unwrap());
+        let binary = std::env::temp_dir().join(format!("simbad-{}-round-trip.bin", std::process::id()));
+        catalog.save_binary(&binary).unwrap();
+        let loaded = Catalog::load_binary(&binary).unwrap();
+        assert_eq!(loaded.stars.iter().map(|s| (s.id, s.pos, &s.identifier)).collect::<Vec<_>>(), catalog.stars.iter().map(|s| (s.id, s.pos, &s.identifier)).collect::<Vec<_>>());
+    }
+
+    // A header claiming far more stars than follow must fail on the missing data, not allocate
+    // for the claim.
+    #[test]
+    fn huge_header_count_is_an_error() {
+        let mut bytes = vec![];
+        binary::write_header(&mut bytes, usize::MAX >> 4).unwrap();
+        let path = std::env::temp_dir().join(format!("simbad-{}-huge-count.bin", std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+        assert!(Catalog::load_binary(&path).is_err());
+    }
+
+    #[test]
+    fn bayer_stars_include_components() {
+        let path = SimbadCsv::new(crate::tests::sample_records()).write_temp("bayer-components.csv").unwrap();
+        let catalog = Catalog::new(crate::import(&path).unwrap());
+        let centaurus = catalog.bayer_stars(Constellation::Centaurus);
+        assert_eq!(centaurus.iter().map(|s| s.identifier.as_str()).collect::<Vec<_>>(), ["* alf Cen A"]);
+        assert_eq!(centaurus[0].constellation, Some(Constellation::Centaurus));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{Band, Catalog, Star, StellarPosition};
+
+// Struct-of-arrays view of a catalog for scans over many stars. Missing magnitudes are NaN, which
+// fails every comparison, so the kernels need no branches on Option.
+#[derive(Default, Clone, Debug)]
+pub struct ColumnarCatalog {
+    pub id: Vec<usize>,
+    pub ra: Vec<f32>,
+    pub dec: Vec<f32>,
+    pub distance: Vec<f32>,
+    pub mag_v: Vec<f32>,
+    pub mag_b: Vec<f32>
+}
+
+impl ColumnarCatalog {
+    pub fn len(&self) -> usize {
+        self.id.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.id.is_empty()
+    }
+
+    pub fn push(&mut self, star: &Star) {
+        let pos = StellarPosition::from(star.pos);
+        self.id.push(star.id);
+        self.ra.push(pos.coord.right_ascension);
+        self.dec.push(pos.coord.declination);
+        self.distance.push(pos.distance);
+        self.mag_v.push(star.photometry.get(Band::V).unwrap_or(f32::NAN));
+        self.mag_b.push(star.photometry.get(Band::B).unwrap_or(f32::NAN));
+    }
+
+    pub fn magnitude_mask(&self, max_mag_v: f32) -> Vec<bool> {
+        self.mag_v.iter().map(|m| *m <= max_mag_v).collect()
+    }
+
+    pub fn distance_mask(&self, max_distance: f32) -> Vec<bool> {
+        self.distance.iter().map(|d| *d <= max_distance).collect()
+    }
+
+    pub fn declination_mask(&self, min_dec: f32, max_dec: f32) -> Vec<bool> {
+        self.dec.iter().map(|d| *d >= min_dec && *d <= max_dec).collect()
+    }
+
+    pub fn magnitude_cut(&self, max_mag_v: f32) -> Vec<usize> {
+        indices(&self.magnitude_mask(max_mag_v))
+    }
+
+    pub fn select(&self, mask: &[bool]) -> ColumnarCatalog {
+        ColumnarCatalog {
+            id: filter(&self.id, mask),
+            ra: filter(&self.ra, mask),
+            dec: filter(&self.dec, mask),
+            distance: filter(&self.distance, mask),
+            mag_v: filter(&self.mag_v, mask),
+            mag_b: filter(&self.mag_b, mask),
+        }
+    }
+}
+
+pub fn combine_masks(a: &[bool], b: &[bool]) -> Vec<bool> {
+    a.iter().zip(b).map(|(a, b)| *a & *b).collect()
+}
+
+pub fn indices(mask: &[bool]) -> Vec<usize> {
+    mask.iter().enumerate().filter_map(|(i, m)| m.then_some(i)).collect()
+}
+
+fn filter<T: Copy>(column: &[T], mask: &[bool]) -> Vec<T> {
+    column.iter().zip(mask).filter_map(|(v, m)| m.then_some(*v)).collect()
+}
+
+impl From<&[Star]> for ColumnarCatalog {
+    fn from(stars: &[Star]) -> Self {
+        let mut columnar = ColumnarCatalog::default();
+        for star in stars {
+            columnar.push(star);
+        }
+        columnar
+    }
+}
+
+impl From<&Catalog> for ColumnarCatalog {
+    fn from(catalog: &Catalog) -> Self {
+        catalog.stars.as_slice().into()
+    }
+}
+
+impl From<Vec<Star>> for ColumnarCatalog {
+    fn from(stars: Vec<Star>) -> Self {
+        stars.as_slice().into()
+    }
+}
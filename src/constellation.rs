@@ -0,0 +1,268 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::SimbadError;
+
+// The 88 IAU constellations, in alphabetical order of their Latin names.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Constellation {
+    Andromeda,
+    Antlia,
+    Apus,
+    Aquarius,
+    Aquila,
+    Ara,
+    Aries,
+    Auriga,
+    Bootes,
+    Caelum,
+    Camelopardalis,
+    Cancer,
+    CanesVenatici,
+    CanisMajor,
+    CanisMinor,
+    Capricornus,
+    Carina,
+    Cassiopeia,
+    Centaurus,
+    Cepheus,
+    Cetus,
+    Chamaeleon,
+    Circinus,
+    Columba,
+    ComaBerenices,
+    CoronaAustralis,
+    CoronaBorealis,
+    Corvus,
+    Crater,
+    Crux,
+    Cygnus,
+    Delphinus,
+    Dorado,
+    Draco,
+    Equuleus,
+    Eridanus,
+    Fornax,
+    Gemini,
+    Grus,
+    Hercules,
+    Horologium,
+    Hydra,
+    Hydrus,
+    Indus,
+    Lacerta,
+    Leo,
+    LeoMinor,
+    Lepus,
+    Libra,
+    Lupus,
+    Lynx,
+    Lyra,
+    Mensa,
+    Microscopium,
+    Monoceros,
+    Musca,
+    Norma,
+    Octans,
+    Ophiuchus,
+    Orion,
+    Pavo,
+    Pegasus,
+    Perseus,
+    Phoenix,
+    Pictor,
+    Pisces,
+    PiscisAustrinus,
+    Puppis,
+    Pyxis,
+    Reticulum,
+    Sagitta,
+    Sagittarius,
+    Scorpius,
+    Sculptor,
+    Scutum,
+    Serpens,
+    Sextans,
+    Taurus,
+    Telescopium,
+    Triangulum,
+    TriangulumAustrale,
+    Tucana,
+    UrsaMajor,
+    UrsaMinor,
+    Vela,
+    Virgo,
+    Volans,
+    Vulpecula
+}
+
+// (abbreviation, name, genitive), indexed by the enum discriminant.
+const CONSTELLATIONS: [(&str, &str, &str); 88] = [
+    ("And", "Andromeda", "Andromedae"),
+    ("Ant", "Antlia", "Antliae"),
+    ("Aps", "Apus", "Apodis"),
+    ("Aqr", "Aquarius", "Aquarii"),
+    ("Aql", "Aquila", "Aquilae"),
+    ("Ara", "Ara", "Arae"),
+    ("Ari", "Aries", "Arietis"),
+    ("Aur", "Auriga", "Aurigae"),
+    ("Boo", "Boötes", "Boötis"),
+    ("Cae", "Caelum", "Caeli"),
+    ("Cam", "Camelopardalis", "Camelopardalis"),
+    ("Cnc", "Cancer", "Cancri"),
+    ("CVn", "Canes Venatici", "Canum Venaticorum"),
+    ("CMa", "Canis Major", "Canis Majoris"),
+    ("CMi", "Canis Minor", "Canis Minoris"),
+    ("Cap", "Capricornus", "Capricorni"),
+    ("Car", "Carina", "Carinae"),
+    ("Cas", "Cassiopeia", "Cassiopeiae"),
+    ("Cen", "Centaurus", "Centauri"),
+    ("Cep", "Cepheus", "Cephei"),
+    ("Cet", "Cetus", "Ceti"),
+    ("Cha", "Chamaeleon", "Chamaeleontis"),
+    ("Cir", "Circinus", "Circini"),
+    ("Col", "Columba", "Columbae"),
+    ("Com", "Coma Berenices", "Comae Berenices"),
+    ("CrA", "Corona Australis", "Coronae Australis"),
+    ("CrB", "Corona Borealis", "Coronae Borealis"),
+    ("Crv", "Corvus", "Corvi"),
+    ("Crt", "Crater", "Crateris"),
+    ("Cru", "Crux", "Crucis"),
+    ("Cyg", "Cygnus", "Cygni"),
+    ("Del", "Delphinus", "Delphini"),
+    ("Dor", "Dorado", "Doradus"),
+    ("Dra", "Draco", "Draconis"),
+    ("Equ", "Equuleus", "Equulei"),
+    ("Eri", "Eridanus", "Eridani"),
+    ("For", "Fornax", "Fornacis"),
+    ("Gem", "Gemini", "Geminorum"),
+    ("Gru", "Grus", "Gruis"),
+    ("Her", "Hercules", "Herculis"),
+    ("Hor", "Horologium", "Horologii"),
+    ("Hya", "Hydra", "Hydrae"),
+    ("Hyi", "Hydrus", "Hydri"),
+    ("Ind", "Indus", "Indi"),
+    ("Lac", "Lacerta", "Lacertae"),
+    ("Leo", "Leo", "Leonis"),
+    ("LMi", "Leo Minor", "Leonis Minoris"),
+    ("Lep", "Lepus", "Leporis"),
+    ("Lib", "Libra", "Librae"),
+    ("Lup", "Lupus", "Lupi"),
+    ("Lyn", "Lynx", "Lyncis"),
+    ("Lyr", "Lyra", "Lyrae"),
+    ("Men", "Mensa", "Mensae"),
+    ("Mic", "Microscopium", "Microscopii"),
+    ("Mon", "Monoceros", "Monocerotis"),
+    ("Mus", "Musca", "Muscae"),
+    ("Nor", "Norma", "Normae"),
+    ("Oct", "Octans", "Octantis"),
+    ("Oph", "Ophiuchus", "Ophiuchi"),
+    ("Ori", "Orion", "Orionis"),
+    ("Pav", "Pavo", "Pavonis"),
+    ("Peg", "Pegasus", "Pegasi"),
+    ("Per", "Perseus", "Persei"),
+    ("Phe", "Phoenix", "Phoenicis"),
+    ("Pic", "Pictor", "Pictoris"),
+    ("Psc", "Pisces", "Piscium"),
+    ("PsA", "Piscis Austrinus", "Piscis Austrini"),
+    ("Pup", "Puppis", "Puppis"),
+    ("Pyx", "Pyxis", "Pyxidis"),
+    ("Ret", "Reticulum", "Reticuli"),
+    ("Sge", "Sagitta", "Sagittae"),
+    ("Sgr", "Sagittarius", "Sagittarii"),
+    ("Sco", "Scorpius", "Scorpii"),
+    ("Scl", "Sculptor", "Sculptoris"),
+    ("Sct", "Scutum", "Scuti"),
+    ("Ser", "Serpens", "Serpentis"),
+    ("Sex", "Sextans", "Sextantis"),
+    ("Tau", "Taurus", "Tauri"),
+    ("Tel", "Telescopium", "Telescopii"),
+    ("Tri", "Triangulum", "Trianguli"),
+    ("TrA", "Triangulum Australe", "Trianguli Australis"),
+    ("Tuc", "Tucana", "Tucanae"),
+    ("UMa", "Ursa Major", "Ursae Majoris"),
+    ("UMi", "Ursa Minor", "Ursae Minoris"),
+    ("Vel", "Vela", "Velorum"),
+    ("Vir", "Virgo", "Virginis"),
+    ("Vol", "Volans", "Volantis"),
+    ("Vul", "Vulpecula", "Vulpeculae")
+];
+
+impl Constellation {
+    pub const ALL: [Constellation; 88] = [
+        Self::Andromeda, Self::Antlia, Self::Apus, Self::Aquarius, Self::Aquila, Self::Ara,
+        Self::Aries, Self::Auriga, Self::Bootes, Self::Caelum, Self::Camelopardalis, Self::Cancer,
+        Self::CanesVenatici, Self::CanisMajor, Self::CanisMinor, Self::Capricornus, Self::Carina, Self::Cassiopeia,
+        Self::Centaurus, Self::Cepheus, Self::Cetus, Self::Chamaeleon, Self::Circinus, Self::Columba,
+        Self::ComaBerenices, Self::CoronaAustralis, Self::CoronaBorealis, Self::Corvus, Self::Crater, Self::Crux,
+        Self::Cygnus, Self::Delphinus, Self::Dorado, Self::Draco, Self::Equuleus, Self::Eridanus,
+        Self::Fornax, Self::Gemini, Self::Grus, Self::Hercules, Self::Horologium, Self::Hydra,
+        Self::Hydrus, Self::Indus, Self::Lacerta, Self::Leo, Self::LeoMinor, Self::Lepus,
+        Self::Libra, Self::Lupus, Self::Lynx, Self::Lyra, Self::Mensa, Self::Microscopium,
+        Self::Monoceros, Self::Musca, Self::Norma, Self::Octans, Self::Ophiuchus, Self::Orion,
+        Self::Pavo, Self::Pegasus, Self::Perseus, Self::Phoenix, Self::Pictor, Self::Pisces,
+        Self::PiscisAustrinus, Self::Puppis, Self::Pyxis, Self::Reticulum, Self::Sagitta, Self::Sagittarius,
+        Self::Scorpius, Self::Sculptor, Self::Scutum, Self::Serpens, Self::Sextans, Self::Taurus,
+        Self::Telescopium, Self::Triangulum, Self::TriangulumAustrale, Self::Tucana, Self::UrsaMajor, Self::UrsaMinor,
+        Self::Vela, Self::Virgo, Self::Volans, Self::Vulpecula
+    ];
+
+    // IAU three letter abbreviation, e.g. "CMa".
+    pub fn abbreviation(&self) -> &'static str {
+        CONSTELLATIONS[*self as usize].0
+    }
+
+    pub fn name(&self) -> &'static str {
+        CONSTELLATIONS[*self as usize].1
+    }
+
+    // Genitive as used in star names, e.g. "Canis Majoris".
+    pub fn genitive(&self) -> &'static str {
+        CONSTELLATIONS[*self as usize].2
+    }
+}
+
+impl Display for Constellation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Constellation {
+    type Err = SimbadError;
+
+    // Accepts the abbreviation, name or genitive, ignoring case, whitespace and the diaeresis in "Boötes".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fold = |s: &str| s.chars().filter(|c| !c.is_whitespace()).map(|c| if c == 'ö' { 'o' } else { c.to_ascii_lowercase() }).collect::<String>();
+        let s = fold(s);
+        Self::ALL.into_iter()
+            .find(|c| [c.abbreviation(), c.name(), c.genitive()].into_iter().any(|v| fold(v) == s))
+            .ok_or(SimbadError::InvalidConstellation)
+    }
+}
+
+// Serialized as the IAU abbreviation.
+impl Serialize for Constellation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.abbreviation())
+    }
+}
+
+impl<'de> Deserialize<'de> for Constellation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+// Matches the serde impls above: the IAU abbreviation as a string.
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Constellation {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Constellation".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let abbreviations = Constellation::ALL.iter().map(Constellation::abbreviation).collect::<Vec<_>>();
+        schemars::json_schema!({ "type": "string", "enum": abbreviations })
+    }
+}
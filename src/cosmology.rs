@@ -0,0 +1,48 @@
+// Flat or curved ΛCDM; H0 in km/s/Mpc.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Cosmology {
+    pub hubble_constant: f64,
+    pub omega_matter: f64,
+    pub omega_lambda: f64
+}
+
+impl Default for Cosmology {
+    // Planck 2018
+    fn default() -> Self {
+        Self {
+            hubble_constant: 67.4,
+            omega_matter: 0.315,
+            omega_lambda: 0.685,
+        }
+    }
+}
+
+impl Cosmology {
+    pub fn new(hubble_constant: f64, omega_matter: f64, omega_lambda: f64) -> Self {
+        Self {
+            hubble_constant,
+            omega_matter,
+            omega_lambda,
+        }
+    }
+
+    pub fn hubble_time(&self) -> f64 {
+        977.792e9 / self.hubble_constant
+    }
+
+    fn e(&self, z: f64) -> f64 {
+        let omega_k = 1. - self.omega_matter - self.omega_lambda;
+        let zp1 = 1. + z;
+        (self.omega_matter * zp1.powi(3) + omega_k * zp1 * zp1 + self.omega_lambda).sqrt()
+    }
+
+    // Years between emission at redshift `z` and today (Simpson's rule).
+    pub fn lookback_time(&self, z: f64) -> f64 {
+        if z <= 0. { return 0.; }
+        let steps = 1000;
+        let h = z / steps as f64;
+        let f = |z: f64| 1. / ((1. + z) * self.e(z));
+        let sum = (1..steps).map(|i| f(i as f64 * h) * if i % 2 == 0 { 2. } else { 4. }).sum::<f64>();
+        self.hubble_time() * h / 3. * (f(0.) + sum + f(z))
+    }
+}
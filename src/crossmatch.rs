@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+use glam::Vec3A;
+use crate::separation::angle_between;
+use crate::{CellKey, Star, StellarPosition};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CrossMatch {
+    // Indices into the two star slices.
+    pub a: usize,
+    pub b: usize,
+    // Radians, at `epoch_b`.
+    pub separation: f32
+}
+
+// Nearest star of `b` within `radius_arcsec` for every star of `a`, after moving the stars of `a`
+// with proper motion data from `epoch_a` to `epoch_b` (Julian dates). Stars without proper motion
+// are compared where they are. Several stars of `a` can match the same star of `b`.
+pub fn crossmatch_epochs(a: &[Star], epoch_a: f64, b: &[Star], epoch_b: f64, radius_arcsec: f32) -> Vec<CrossMatch> {
+    let years = (epoch_b - epoch_a) / 365.25;
+    let radius = (radius_arcsec / 3600.).to_radians();
+    let mut cells: HashMap<CellKey, Vec<(usize, Vec3A)>> = HashMap::new();
+    for (index, star) in b.iter().enumerate() {
+        let coord = StellarPosition::from(star.pos).coord;
+        cells.entry(coord.cell_key(radius_arcsec)).or_default().push((index, Vec3A::from(coord.unit_vector())));
+    }
+    a.iter().enumerate().filter_map(|(index, star)| {
+        let coord = StellarPosition::from(star.pos).coord;
+        let coord = match star.proper_motion {
+            Some(pm) => pm.apply(&coord, years),
+            None => coord
+        };
+        let v = Vec3A::from(coord.unit_vector());
+        coord.cell_keys_near(radius_arcsec).iter()
+            .filter_map(|cell| cells.get(cell))
+            .flatten()
+            .map(|(other, other_v)| CrossMatch { a: index, b: *other, separation: angle_between(v, *other_v) })
+            .filter(|m| m.separation <= radius)
+            .min_by(|x, y| x.separation.total_cmp(&y.separation))
+    }).collect()
+}
@@ -0,0 +1,99 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use csv::StringRecord;
+use serde::{Deserialize, Deserializer};
+use crate::schema::ResolvedHeader;
+
+// SIMBAD writes "~" (and some tools "--") for missing values; treat those like empty fields.
+pub(crate) fn null_marker<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = Option::<String>::deserialize(deserializer)?;
+    match value.as_deref().map(str::trim) {
+        None | Some("") | Some("~") | Some("--") => Ok(None),
+        Some(v) => v.parse().map(Some).map_err(serde::de::Error::custom)
+    }
+}
+
+// Borrowing counterparts of `null_marker` for `SlimRecord`, so nothing is allocated for values
+// that are never kept.
+pub(crate) fn borrowed_null_marker<'de, D>(deserializer: D) -> Result<Option<&'de str>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<&'de str>::deserialize(deserializer)?;
+    Ok(value.map(str::trim).filter(|v| !matches!(*v, "" | "~" | "--")))
+}
+
+pub(crate) fn borrowed_parse<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    borrowed_null_marker(deserializer)?.map(|v| v.parse().map_err(serde::de::Error::custom)).transpose()
+}
+
+// `NumberFormat::Tolerant`: rewrites the numeric fields of a row so the strict parsers above accept
+// them. Other fields are passed through untouched.
+pub(crate) fn tolerant_row(row: &StringRecord, header: &ResolvedHeader) -> StringRecord {
+    let mut tolerant = row.iter().enumerate()
+        .map(|(index, field)| if header.is_numeric(index) { tolerant_number(field) } else { field.to_string() })
+        .collect::<StringRecord>();
+    tolerant.set_position(row.position().cloned());
+    tolerant
+}
+
+// Drops whitespace and one pair of wrapping quotes, then turns a decimal comma into a point in
+// every token that has one and no point, e.g. "\"0,742\"" -> "0.742" and "12,5 -3,1" -> "12.5 -3.1".
+fn tolerant_number(field: &str) -> String {
+    let mut field = field.trim();
+    for quote in ['"', '\''] {
+        if field.len() >= 2 && field.starts_with(quote) && field.ends_with(quote) {
+            field = field[1..field.len() - 1].trim();
+        }
+    }
+    field.split_whitespace()
+        .map(|token| if token.matches(',').count() == 1 && !token.contains('.') { token.replace(',', ".") } else { token.to_string() })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{RecordBuilder, SimbadCsv};
+    use crate::{import_with_options, Band, ImportOptions, NumberFormat};
+
+    fn spreadsheet_export() -> std::path::PathBuf {
+        let record = RecordBuilder::new(1, "* alf Cen A").coordinates("14 39 36.49 -60 50 02.3").parallax(742.12)
+            .magnitude(Band::V, 0.01).proper_motion(-3679.25, 473.67).spectral_type("G2V").build();
+        let text = SimbadCsv::new(vec![record]).render()
+            .replace("742.12", "\" 742,12 \"")
+            .replace("0.01", "0,01")
+            .replace("-3679.25 473.67", "-3679,25 473,67");
+        let path = std::env::temp_dir().join(format!("simbad-{}-decimal-comma.csv", std::process::id()));
+        std::fs::write(&path, text).unwrap();
+        path
+    }
+
+    #[test]
+    fn tolerant_numbers_read_decimal_commas_and_quotes() {
+        let options = ImportOptions::new().numbers(NumberFormat::Tolerant);
+        let report = import_with_options(spreadsheet_export(), &options).unwrap();
+        let star = &report.records[0];
+        assert_eq!(star.photometry.get(Band::V), Some(0.01));
+        assert!((star.pos.length() - 4.395).abs() < 0.01);
+        let pm = star.proper_motion.unwrap();
+        assert!((pm.ra - -3679.25).abs() < 1e-3 && (pm.dec - 473.67).abs() < 1e-3);
+    }
+
+    #[test]
+    fn strict_numbers_skip_the_row() {
+        let report = import_with_options(spreadsheet_export(), &ImportOptions::new()).unwrap();
+        assert!(report.records.is_empty());
+        assert_eq!(report.skipped.len(), 1);
+    }
+}
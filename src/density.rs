@@ -0,0 +1,124 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use crate::{healpix, Angle, EquatorialCoordinate, Star, StellarPosition};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Binning {
+    Grid { ra_bins: usize, dec_bins: usize },
+    Healpix { nside: u32 }
+}
+
+impl Binning {
+    pub fn cell_count(&self) -> usize {
+        match *self {
+            Self::Grid { ra_bins, dec_bins } => ra_bins * dec_bins,
+            Self::Healpix { nside } => healpix::npix(nside) as usize
+        }
+    }
+
+    pub fn cell_of(&self, coord: &EquatorialCoordinate) -> usize {
+        match *self {
+            Self::Grid { ra_bins, dec_bins } => {
+                let ra = (coord.right_ascension.wrap_positive() / (2. * PI) * ra_bins as f32) as usize;
+                let dec = ((coord.declination + FRAC_PI_2) / PI * dec_bins as f32) as usize;
+                dec.min(dec_bins - 1) * ra_bins + ra.min(ra_bins - 1)
+            }
+            Self::Healpix { nside } => healpix::ang2pix_nest(nside, coord) as usize
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DensityMap {
+    pub binning: Binning,
+    pub counts: Vec<u32>
+}
+
+impl DensityMap {
+    pub fn new(binning: Binning) -> Self {
+        Self {
+            binning,
+            counts: vec![0; binning.cell_count()],
+        }
+    }
+
+    pub fn add(&mut self, coord: &EquatorialCoordinate) {
+        let cell = self.binning.cell_of(coord);
+        self.counts[cell] += 1;
+    }
+
+    pub fn count_at(&self, coord: &EquatorialCoordinate) -> u32 {
+        self.counts[self.binning.cell_of(coord)]
+    }
+
+    // Grid maps get one row per cell with its bounds in degrees, HEALPix maps one row per pixel.
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn std::error::Error>> {
+        let mut wtr = csv::WriterBuilder::new().delimiter(b';').from_path(path)?;
+        match self.binning {
+            Binning::Grid { ra_bins, dec_bins } => {
+                wtr.write_record(["ra_min", "ra_max", "dec_min", "dec_max", "count"])?;
+                let (ra_step, dec_step) = (360. / ra_bins as f32, 180. / dec_bins as f32);
+                for (cell, count) in self.counts.iter().enumerate() {
+                    let (ra, dec) = ((cell % ra_bins) as f32, (cell / ra_bins) as f32);
+                    wtr.write_record([ra * ra_step, (ra + 1.) * ra_step, dec * dec_step - 90., (dec + 1.) * dec_step - 90., *count as f32].map(|v| v.to_string()))?;
+                }
+            }
+            Binning::Healpix { .. } => {
+                wtr.write_record(["pixel", "count"])?;
+                for (pixel, count) in self.counts.iter().enumerate() {
+                    wtr.write_record([pixel.to_string(), count.to_string()])?;
+                }
+            }
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    // Minimal single-HDU FITS image with 32 bit integer counts: 2D (RA x Dec) for grids,
+    // 1D with the usual PIXTYPE/ORDERING/NSIDE keywords for HEALPix.
+    pub fn write_fits<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn std::error::Error>> {
+        let mut cards = vec![fits_card("SIMPLE", "T"), fits_card("BITPIX", "32")];
+        match self.binning {
+            Binning::Grid { ra_bins, dec_bins } => {
+                cards.push(fits_card("NAXIS", "2"));
+                cards.push(fits_card("NAXIS1", &ra_bins.to_string()));
+                cards.push(fits_card("NAXIS2", &dec_bins.to_string()));
+            }
+            Binning::Healpix { nside } => {
+                cards.push(fits_card("NAXIS", "1"));
+                cards.push(fits_card("NAXIS1", &self.counts.len().to_string()));
+                cards.push(fits_card("PIXTYPE", "'HEALPIX '"));
+                cards.push(fits_card("ORDERING", "'NESTED  '"));
+                cards.push(fits_card("NSIDE", &nside.to_string()));
+            }
+        }
+        cards.push(format!("{:<80}", "END"));
+        let mut header = cards.concat().into_bytes();
+        header.resize(header.len().div_ceil(2880) * 2880, b' ');
+        let mut data = self.counts.iter().flat_map(|c| (*c as i32).to_be_bytes()).collect::<Vec<_>>();
+        data.resize(data.len().div_ceil(2880) * 2880, 0);
+        let mut w = BufWriter::new(File::create(path)?);
+        w.write_all(&header)?;
+        w.write_all(&data)?;
+        w.flush()?;
+        Ok(())
+    }
+}
+
+fn fits_card(keyword: &str, value: &str) -> String {
+    if value.starts_with('\'') {
+        format!("{:<8}= {:<70}", keyword, value)
+    } else {
+        format!("{:<8}= {:>20}{:50}", keyword, value, "")
+    }
+}
+
+pub fn density_map(stars: &[Star], binning: Binning) -> DensityMap {
+    let mut map = DensityMap::new(binning);
+    for star in stars {
+        map.add(&StellarPosition::from(star.pos).coord);
+    }
+    map
+}
@@ -0,0 +1,190 @@
+use crate::Constellation;
+
+// SIMBAD abbreviation, Unicode letter and spelled-out name, in alphabet order.
+pub const GREEK_LETTERS: [(&str, char, &str); 24] = [
+    ("alf", 'α', "Alpha"),
+    ("bet", 'β', "Beta"),
+    ("gam", 'γ', "Gamma"),
+    ("del", 'δ', "Delta"),
+    ("eps", 'ε', "Epsilon"),
+    ("zet", 'ζ', "Zeta"),
+    ("eta", 'η', "Eta"),
+    ("tet", 'θ', "Theta"),
+    ("iot", 'ι', "Iota"),
+    ("kap", 'κ', "Kappa"),
+    ("lam", 'λ', "Lambda"),
+    ("mu.", 'μ', "Mu"),
+    ("nu.", 'ν', "Nu"),
+    ("ksi", 'ξ', "Xi"),
+    ("omi", 'ο', "Omicron"),
+    ("pi.", 'π', "Pi"),
+    ("rho", 'ρ', "Rho"),
+    ("sig", 'σ', "Sigma"),
+    ("tau", 'τ', "Tau"),
+    ("ups", 'υ', "Upsilon"),
+    ("phi", 'φ', "Phi"),
+    ("khi", 'χ', "Chi"),
+    ("psi", 'ψ', "Psi"),
+    ("ome", 'ω', "Omega")
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Designation {
+    // `letter` indexes GREEK_LETTERS, `index` is the superscript in e.g. "alf02 Cap".
+    Bayer { letter: usize, index: Option<u8>, constellation: Constellation },
+    Flamsteed { number: u16, constellation: Constellation }
+}
+
+impl Designation {
+    pub fn constellation(&self) -> Constellation {
+        match self {
+            Self::Bayer { constellation, .. } | Self::Flamsteed { constellation, .. } => *constellation
+        }
+    }
+}
+
+// Parses SIMBAD style "* alf Ori", "* alf02 Cap" and "* 58 Ori" identifiers.
+pub fn parse_designation(identifier: &str) -> Option<Designation> {
+    let mut tokens = identifier.trim().strip_prefix('*')?.split_whitespace();
+    let (star, constellation) = (tokens.next()?, tokens.next()?);
+    if tokens.next().is_some() || constellation.len() != 3 || !constellation.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let constellation = constellation.parse::<Constellation>().ok()?;
+    if let Ok(number) = star.parse::<u16>() {
+        return Some(Designation::Flamsteed { number, constellation });
+    }
+    let (letter, rest) = letter_prefix(star)?;
+    let index = if rest.is_empty() { None } else { Some(rest.parse::<u8>().ok()?) };
+    Some(Designation::Bayer { letter, index, constellation })
+}
+
+// The Greek letter `star` starts with and what follows it. SIMBAD writes χ as "khi", other
+// catalogues as "chi"; both are read.
+fn letter_prefix(star: &str) -> Option<(usize, &str)> {
+    GREEK_LETTERS.iter().enumerate().find_map(|(letter, (abbrev, _, name))| {
+        star.strip_prefix(abbrev)
+            .or_else(|| (*name == "Chi").then(|| star.strip_prefix("chi")).flatten())
+            .map(|rest| (letter, rest))
+    })
+}
+
+// How `Star::display_name` and `convert_greek` write Greek letters: "α² Cap", "Alpha-2 Capricorni"
+// or "alf02 Cap".
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DisplayNameStyle {
+    #[default]
+    Unicode,
+    SpelledOut,
+    Abbreviation
+}
+
+const SUPERSCRIPT_DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+impl Designation {
+    pub fn format(&self, style: DisplayNameStyle) -> String {
+        let constellation = self.constellation();
+        let constellation = match style {
+            DisplayNameStyle::SpelledOut => constellation.genitive(),
+            _ => constellation.abbreviation()
+        };
+        match self {
+            Self::Bayer { letter, index, .. } => format!("{} {}", format_greek(*letter, *index, style), constellation),
+            Self::Flamsteed { number, .. } => format!("{} {}", number, constellation)
+        }
+    }
+}
+
+// Index into GREEK_LETTERS for "alf", "α" or "Alpha" (any case), with the trailing '.' of "mu.",
+// "nu." and "pi." optional.
+pub fn greek_letter(token: &str) -> Option<usize> {
+    let mut chars = token.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return GREEK_LETTERS.iter().position(|(_, letter, _)| *letter == c);
+    }
+    GREEK_LETTERS.iter().position(|(abbrev, _, name)| {
+        token.eq_ignore_ascii_case(abbrev) || token.eq_ignore_ascii_case(abbrev.trim_end_matches('.')) || token.eq_ignore_ascii_case(name)
+    })
+}
+
+// Splits "alf02", "α²" or "Alpha-2" into the letter and its superscript index.
+fn parse_greek_token(token: &str) -> Option<(usize, Option<u8>)> {
+    let digits = token.char_indices()
+        .find(|(_, c)| c.is_ascii_digit() || SUPERSCRIPT_DIGITS.contains(c))
+        .map_or(token.len(), |(i, _)| i);
+    let letter = greek_letter(token[..digits].trim_end_matches('-'))?;
+    let index = &token[digits..];
+    if index.is_empty() {
+        return Some((letter, None));
+    }
+    let index = index.chars()
+        .map(|c| c.to_digit(10).or_else(|| SUPERSCRIPT_DIGITS.iter().position(|d| *d == c).map(|d| d as u32)))
+        .try_fold(0u32, |n, d| n.checked_mul(10)?.checked_add(d?))?;
+    Some((letter, Some(u8::try_from(index).ok()?)))
+}
+
+fn format_greek(letter: usize, index: Option<u8>, style: DisplayNameStyle) -> String {
+    let (abbrev, unicode, name) = GREEK_LETTERS[letter];
+    match (style, index) {
+        (DisplayNameStyle::Unicode, None) => unicode.to_string(),
+        (DisplayNameStyle::Unicode, Some(index)) => std::iter::once(unicode)
+            .chain(index.to_string().chars().map(|d| SUPERSCRIPT_DIGITS[d as usize - '0' as usize]))
+            .collect(),
+        (DisplayNameStyle::SpelledOut, None) => name.to_string(),
+        (DisplayNameStyle::SpelledOut, Some(index)) => format!("{}-{}", name, index),
+        (DisplayNameStyle::Abbreviation, None) => abbrev.to_string(),
+        (DisplayNameStyle::Abbreviation, Some(index)) => format!("{}{:02}", abbrev, index)
+    }
+}
+
+// Rewrites the Greek letters of a name or identifier in `style`, e.g. "* alf Cen" -> "* α Cen".
+// Only a letter directly followed by a constellation counts, and that constellation is never
+// read as a letter itself: "* pi. Tau" -> "* π Tau", while "V* CE Tau" stays as it is. With
+// `SpelledOut` the constellation becomes its genitive ("Alpha Centauri"). Whitespace is
+// collapsed; everything else is left as it is.
+pub fn convert_greek(name: &str, style: DisplayNameStyle) -> String {
+    let tokens = name.split_whitespace().collect::<Vec<_>>();
+    let mut out = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+    while i < tokens.len() {
+        let letter = parse_greek_token(tokens[i]);
+        let constellation = tokens.get(i + 1).and_then(|token| token.parse::<Constellation>().ok());
+        match (letter, constellation) {
+            (Some((letter, index)), Some(constellation)) => {
+                out.push(format_greek(letter, index, style));
+                out.push(match style {
+                    DisplayNameStyle::SpelledOut => constellation.genitive().to_string(),
+                    _ => tokens[i + 1].to_string()
+                });
+                i += 2;
+            }
+            _ => {
+                out.push(tokens[i].to_string());
+                i += 1;
+            }
+        }
+    }
+    out.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_chi_as_khi_or_chi() {
+        let chi = GREEK_LETTERS.iter().position(|(abbrev, _, _)| *abbrev == "khi").unwrap();
+        for identifier in ["* khi Cyg", "* chi Cyg"] {
+            assert_eq!(parse_designation(identifier), Some(Designation::Bayer { letter: chi, index: None, constellation: Constellation::Cygnus }), "{}", identifier);
+        }
+        assert_eq!(parse_designation("* chi01 Ori"), Some(Designation::Bayer { letter: chi, index: Some(1), constellation: Constellation::Orion }));
+    }
+
+    #[test]
+    fn converts_only_letters_before_a_constellation() {
+        assert_eq!(convert_greek("* pi. Tau", DisplayNameStyle::Unicode), "* π Tau");
+        assert_eq!(convert_greek("V* CE Tau", DisplayNameStyle::Unicode), "V* CE Tau");
+        assert_eq!(convert_greek("* alf Cen A", DisplayNameStyle::SpelledOut), "* Alpha Centauri A");
+        assert_eq!(convert_greek("Alpha Centauri", DisplayNameStyle::Unicode), "α Centauri");
+    }
+}
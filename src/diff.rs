@@ -0,0 +1,152 @@
+use std::collections::{HashMap, HashSet};
+use crate::{Band, Catalog, Star, StarKey};
+
+// Differences smaller than these count as unchanged. Positions are held as f32 vectors in
+// light-years, good to roughly 10-25 mas, so a position tolerance much below 0.05" reports
+// rounding noise as movement.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DiffTolerance {
+    pub position_arcsec: f32,
+    pub magnitude: f32,
+    // Relative, e.g. 0.001 for 0.1 %.
+    pub distance: f32
+}
+
+impl Default for DiffTolerance {
+    fn default() -> Self {
+        Self { position_arcsec: 0.05, magnitude: 0.005, distance: 0.001 }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    Position { shift_arcsec: f32 },
+    // Light-years.
+    Distance { old: f32, new: f32 },
+    Magnitude { band: Band, old: Option<f32>, new: Option<f32> },
+    SpectralType { old: String, new: String },
+    Name { old: String, new: String }
+}
+
+#[derive(Clone, Debug)]
+pub struct StarChange<'a> {
+    pub old: &'a Star,
+    pub new: &'a Star,
+    pub changes: Vec<Change>
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CatalogDiff<'a> {
+    // Only in the other catalog.
+    pub added: Vec<&'a Star>,
+    // Only in this catalog.
+    pub removed: Vec<&'a Star>,
+    pub changed: Vec<StarChange<'a>>
+}
+
+impl CatalogDiff<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl Catalog {
+    // Pairs stars by `StarKey` (row id for stars without one) and reports what `other` added,
+    // removed or changed beyond `tolerance`, e.g. to check a re-export or a crate upgrade. Each
+    // list is in catalog order.
+    pub fn diff<'a>(&'a self, other: &'a Catalog, tolerance: DiffTolerance) -> CatalogDiff<'a> {
+        let new_stars: HashMap<DiffKey, &Star> = other.stars.iter().map(|star| (DiffKey::of(star), star)).collect();
+        let old_keys: HashSet<DiffKey> = self.stars.iter().map(DiffKey::of).collect();
+        let mut diff = CatalogDiff::default();
+        for old in &self.stars {
+            match new_stars.get(&DiffKey::of(old)) {
+                Some(new) => {
+                    let changes = compare(old, new, &tolerance);
+                    if !changes.is_empty() {
+                        diff.changed.push(StarChange { old, new, changes });
+                    }
+                }
+                None => diff.removed.push(old)
+            }
+        }
+        diff.added = other.stars.iter().filter(|star| !old_keys.contains(&DiffKey::of(star))).collect();
+        diff
+    }
+}
+
+// Stars without a `StarKey` (no identifier or name) are paired by their row id instead.
+#[derive(PartialEq, Eq, Hash)]
+enum DiffKey {
+    Key(StarKey),
+    Id(usize)
+}
+
+impl DiffKey {
+    fn of(star: &Star) -> Self {
+        star.key().map_or(Self::Id(star.id), Self::Key)
+    }
+}
+
+fn compare(old: &Star, new: &Star, tolerance: &DiffTolerance) -> Vec<Change> {
+    let mut changes = vec![];
+    // Angle between the stored directions, in f64 and independent of the distances; going through
+    // RA/Dec would add the rounding of both conversions.
+    let (a, b) = (old.pos.as_dvec3(), new.pos.as_dvec3());
+    let shift_arcsec = (a.cross(b).length().atan2(a.dot(b)).to_degrees() * 3600.) as f32;
+    if shift_arcsec > tolerance.position_arcsec {
+        changes.push(Change::Position { shift_arcsec });
+    }
+    let (a, b) = (old.pos.length(), new.pos.length());
+    if (a - b).abs() > tolerance.distance * a.abs().max(b.abs()) {
+        changes.push(Change::Distance { old: a, new: b });
+    }
+    for band in Band::ALL {
+        let (a, b) = (old.photometry.get(band), new.photometry.get(band));
+        let changed = match (a, b) {
+            (Some(a), Some(b)) => (a - b).abs() > tolerance.magnitude,
+            (a, b) => a.is_some() != b.is_some()
+        };
+        if changed {
+            changes.push(Change::Magnitude { band, old: a, new: b });
+        }
+    }
+    if old.class != new.class {
+        changes.push(Change::SpectralType { old: old.class.clone(), new: new.class.clone() });
+    }
+    if old.name != new.name {
+        changes.push(Change::Name { old: old.name.clone(), new: new.name.clone() });
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::SimbadCsv;
+    use crate::{import, Catalog, Change, DiffTolerance, EquatorialCoordinate, Record, StellarPosition};
+
+    #[test]
+    fn reexported_catalog_diffs_as_unchanged() {
+        let path = SimbadCsv::new(crate::tests::sample_records()).write_temp("diff-original.csv").unwrap();
+        let original = Catalog::new(import(&path).unwrap());
+        let records = original.stars.iter().map(Record::from).collect::<Vec<_>>();
+        let path = SimbadCsv::new(records).write_temp("diff-reexport.csv").unwrap();
+        let reexported = Catalog::new(import(&path).unwrap());
+        assert_eq!(reexported.stars.len(), original.stars.len());
+        let diff = original.diff(&reexported, DiffTolerance::default());
+        assert!(diff.is_empty(), "{:?}", diff.changed.iter().map(|c| &c.changes).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn reports_a_tenth_of_an_arcsecond() {
+        let path = SimbadCsv::new(crate::tests::sample_records()).write_temp("diff-shift.csv").unwrap();
+        let original = Catalog::new(import(&path).unwrap());
+        let mut moved = original.clone();
+        let pos = StellarPosition::from(moved.stars[0].pos);
+        let dec = pos.coord.declination + (0.1f32 / 3600.).to_radians();
+        moved.stars[0].pos = StellarPosition { coord: EquatorialCoordinate::new(pos.coord.right_ascension, dec), ..pos }.into();
+        let diff = original.diff(&moved, DiffTolerance::default());
+        assert_eq!(diff.changed.len(), 1);
+        let [Change::Position { shift_arcsec }] = diff.changed[0].changes[..] else { panic!("{:?}", diff.changed[0].changes) };
+        assert!((shift_arcsec - 0.1).abs() < 0.03, "{}", shift_arcsec);
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{EquatorialCoordinate, GalacticCoordinate, Star, StellarPosition};
+
+const DEG: f32 = std::f32::consts::PI / 180.;
+
+// Well-known reference directions, ICRS/J2000.
+
+// Sgr A* region, galactic (l, b) = (0°, 0°).
+pub const GALACTIC_CENTER: EquatorialCoordinate = EquatorialCoordinate { right_ascension: 266.4051 * DEG, declination: -28.93617 * DEG };
+
+pub const GALACTIC_ANTICENTER: EquatorialCoordinate = EquatorialCoordinate { right_ascension: 86.4051 * DEG, declination: 28.93617 * DEG };
+
+pub const NORTH_GALACTIC_POLE: EquatorialCoordinate = EquatorialCoordinate { right_ascension: 192.85948 * DEG, declination: 27.12825 * DEG };
+
+pub const SOUTH_GALACTIC_POLE: EquatorialCoordinate = EquatorialCoordinate { right_ascension: 12.85948 * DEG, declination: -27.12825 * DEG };
+
+// Where the Sun is heading relative to the LSR, for `SolarMotion::SCHOENRICH_2010`:
+// (l, b) = (47.8°, 23.7°).
+pub const SOLAR_APEX: EquatorialCoordinate = EquatorialCoordinate { right_ascension: 267.058 * DEG, declination: 23.097 * DEG };
+
+// Planck 2018: (l, b) = (264.021°, 48.253°).
+pub const CMB_DIPOLE: EquatorialCoordinate = EquatorialCoordinate { right_ascension: 167.942 * DEG, declination: -6.944 * DEG };
+
+impl Star {
+    // Galactic latitude in radians, positive north of the plane.
+    pub fn angle_from_galactic_plane(&self) -> f32 {
+        GalacticCoordinate::from(StellarPosition::from(self.pos).coord).latitude
+    }
+
+    // Angular distance in radians to a direction such as `GALACTIC_CENTER`.
+    pub fn angle_to(&self, direction: &EquatorialCoordinate) -> f32 {
+        StellarPosition::from(self.pos).coord.separation(direction)
+    }
+}
@@ -0,0 +1,192 @@
+use std::fmt::{Display, Formatter};
+
+pub const LIGHT_YEARS_PER_PARSEC: f32 = 3.26156;
+
+#[derive(Default, Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Distance {
+    pub parsecs: f32
+}
+
+impl Distance {
+    pub fn from_parsecs(parsecs: f32) -> Self {
+        Self { parsecs }
+    }
+
+    pub fn from_light_years(light_years: f32) -> Self {
+        Self { parsecs: light_years / LIGHT_YEARS_PER_PARSEC }
+    }
+
+    pub fn parsecs(&self) -> f32 {
+        self.parsecs
+    }
+
+    pub fn light_years(&self) -> f32 {
+        self.parsecs * LIGHT_YEARS_PER_PARSEC
+    }
+
+    // Years, ignoring cosmological expansion.
+    pub fn light_travel_time(&self) -> f32 {
+        self.light_years()
+    }
+}
+
+impl Display for Distance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.2} pc", self.parsecs)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParallaxError {
+    NonPositive,
+    InvalidUncertainty
+}
+
+impl Display for ParallaxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for ParallaxError {}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub enum DistancePrior {
+    #[default]
+    None,
+    // Exponentially decreasing space density (Bailer-Jones 2015), as used for Gaia distances.
+    ExponentiallyDecreasing { length_scale: Distance }
+}
+
+// `distance` is the mode, `lower`/`upper` bound the central 68% interval.
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub struct DistanceEstimate {
+    pub distance: Distance,
+    pub lower: Distance,
+    pub upper: Distance
+}
+
+pub fn distance_from_parallax(plx_mas: f32, plx_err_mas: f32) -> Result<DistanceEstimate, ParallaxError> {
+    distance_from_parallax_with_prior(plx_mas, plx_err_mas, DistancePrior::None)
+}
+
+// Without a prior the parallax must be positive; with the EDSD prior any parallax gives a finite estimate.
+pub fn distance_from_parallax_with_prior(plx_mas: f32, plx_err_mas: f32, prior: DistancePrior) -> Result<DistanceEstimate, ParallaxError> {
+    if !plx_err_mas.is_finite() || plx_err_mas < 0. {
+        return Err(ParallaxError::InvalidUncertainty);
+    }
+    match prior {
+        DistancePrior::None => {
+            if plx_mas.is_nan() || plx_mas <= 0. {
+                return Err(ParallaxError::NonPositive);
+            }
+            let distance = 1000. / plx_mas;
+            let sigma = 1000. * plx_err_mas / (plx_mas * plx_mas);
+            Ok(DistanceEstimate {
+                distance: Distance::from_parsecs(distance),
+                lower: Distance::from_parsecs((distance - sigma).max(0.)),
+                upper: Distance::from_parsecs(distance + sigma),
+            })
+        }
+        DistancePrior::ExponentiallyDecreasing { length_scale } => {
+            if plx_err_mas == 0. {
+                return Err(ParallaxError::InvalidUncertainty);
+            }
+            Ok(edsd_estimate(plx_mas as f64 / 1000., plx_err_mas as f64 / 1000., length_scale.parsecs as f64))
+        }
+    }
+}
+
+// Parallax and error in arcsec, length scale in pc.
+fn edsd_estimate(plx: f64, err: f64, length_scale: f64) -> DistanceEstimate {
+    let var = err * err;
+    let mode = cubic_real_roots(1. / length_scale, -2., plx / var, -1. / var)
+        .into_iter()
+        .filter(|r| *r > 0.)
+        .fold(None, |best: Option<f64>, r| Some(match best {
+            Some(b) if plx >= 0. => b.min(r),
+            Some(b) => b.max(r),
+            None => r
+        }))
+        .unwrap_or(2. * length_scale);
+    // Integrated in ln r over the range the likelihood allows, 1/r within 10σ of 1/mode, which
+    // keeps the steps a small fraction of the interval for precise and imprecise parallaxes alike.
+    // Log densities are taken relative to the mode so nothing underflows.
+    let log_posterior = |r: f64| 2. * r.ln() - r / length_scale - (plx - 1. / r).powi(2) / (2. * var);
+    let peak = log_posterior(mode);
+    let near = 1. / mode;
+    let r_min = 1. / (near + 10. * err);
+    let r_max = if near > 10. * err { 1. / (near - 10. * err) } else { f64::INFINITY }.min(mode + 50. * length_scale);
+    let steps = 10000;
+    let (u_min, du) = (r_min.ln(), (r_max.ln() - r_min.ln()) / steps as f64);
+    // d(probability) = p(r) r du.
+    let density = |i: usize| {
+        let r = (u_min + i as f64 * du).exp();
+        (log_posterior(r) - peak).exp() * r
+    };
+    let mut cdf = Vec::with_capacity(steps + 1);
+    cdf.push(0.);
+    let mut total = 0.;
+    for i in 1..=steps {
+        total += (density(i - 1) + density(i)) / 2. * du;
+        cdf.push(total);
+    }
+    let quantile = |q: f64| {
+        let target = q * total;
+        let i = cdf.partition_point(|c| *c < target).clamp(1, steps);
+        let step = cdf[i] - cdf[i - 1];
+        let fraction = if step > 0. { (target - cdf[i - 1]) / step } else { 0. };
+        (u_min + (i as f64 - 1. + fraction) * du).exp()
+    };
+    DistanceEstimate {
+        distance: Distance::from_parsecs(mode as f32),
+        lower: Distance::from_parsecs(quantile(0.1587) as f32),
+        upper: Distance::from_parsecs(quantile(0.8413) as f32),
+    }
+}
+
+fn cubic_real_roots(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    let (b, c, d) = (b / a, c / a, d / a);
+    let p = c - b * b / 3.;
+    let q = 2. * b * b * b / 27. - b * c / 3. + d;
+    let shift = -b / 3.;
+    let disc = q * q / 4. + p * p * p / 27.;
+    if disc > 0. {
+        let s = disc.sqrt();
+        vec![(-q / 2. + s).cbrt() + (-q / 2. - s).cbrt() + shift]
+    } else {
+        let r = (-p / 3.).sqrt();
+        let phi = (-q / (2. * r * r * r)).clamp(-1., 1.).acos();
+        (0..3).map(|k| 2. * r * ((phi - 2. * std::f64::consts::PI * k as f64) / 3.).cos() + shift).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edsd(plx_mas: f32, plx_err_mas: f32) -> DistanceEstimate {
+        let prior = DistancePrior::ExponentiallyDecreasing { length_scale: Distance::from_parsecs(1350.) };
+        distance_from_parallax_with_prior(plx_mas, plx_err_mas, prior).unwrap()
+    }
+
+    #[test]
+    fn edsd_interval_of_precise_parallax_matches_error_propagation() {
+        for (plx, err) in [(100., 0.1), (10., 0.05), (2., 0.01)] {
+            let estimate = edsd(plx, err);
+            assert!(estimate.lower < estimate.distance && estimate.distance < estimate.upper, "{:?}", estimate);
+            let half_width = (estimate.upper.parsecs - estimate.lower.parsecs) / 2.;
+            let expected = 1000. * err / (plx * plx);
+            assert!((half_width / expected - 1.).abs() < 0.05, "plx {}: half width {} pc, expected {} pc", plx, half_width, expected);
+        }
+    }
+
+    #[test]
+    fn edsd_interval_of_poor_parallax_contains_mode() {
+        for (plx, err) in [(1., 0.5), (0.2, 0.3), (-0.5, 1.)] {
+            let estimate = edsd(plx, err);
+            assert!(estimate.lower < estimate.distance && estimate.distance < estimate.upper, "{:?}", estimate);
+            assert!(estimate.upper.parsecs.is_finite());
+        }
+    }
+}
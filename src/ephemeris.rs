@@ -0,0 +1,60 @@
+use glam::DVec3;
+use crate::{EquatorialCoordinate, Observer};
+
+pub(crate) const J2000: f64 = 2451545.0;
+pub(crate) const AU_PER_LIGHT_YEAR: f64 = 63241.077;
+pub(crate) const SPEED_OF_LIGHT: f64 = 173.144633; // AU per day
+
+// Low precision solar coordinates (Astronomical Almanac), good to ~0.01° between 1950 and 2050.
+pub(crate) fn sun_position(jd: f64) -> DVec3 {
+    let n = jd - J2000;
+    let mean_longitude = 280.460 + 0.9856474 * n;
+    let mean_anomaly = (357.528 + 0.9856003 * n).to_radians();
+    let longitude = (mean_longitude + 1.915 * mean_anomaly.sin() + 0.020 * (2. * mean_anomaly).sin()).to_radians();
+    let radius = 1.00014 - 0.01671 * mean_anomaly.cos() - 0.00014 * (2. * mean_anomaly).cos();
+    let obliquity = (23.439 - 0.0000004 * n).to_radians();
+    DVec3::new(
+        radius * longitude.cos(),
+        radius * obliquity.cos() * longitude.sin(),
+        radius * obliquity.sin() * longitude.sin(),
+    )
+}
+
+pub(crate) fn earth_position(jd: f64) -> DVec3 {
+    -sun_position(jd)
+}
+
+pub(crate) fn earth_velocity(jd: f64) -> DVec3 {
+    earth_position(jd + 0.5) - earth_position(jd - 0.5)
+}
+
+// Mean circular heliocentric orbits of Jupiter and Saturn: (longitude at J2000 in degrees,
+// degrees per day, semi-major axis in AU, mass relative to the Sun).
+const GIANT_PLANETS: [(f64, f64, f64, f64); 2] = [
+    (34.35, 0.0830912, 5.2026, 1. / 1047.35),
+    (50.08, 0.0334597, 9.5549, 1. / 3497.9)
+];
+
+// Offset of the Sun's centre from the solar-system barycentre in equatorial AU. Only the giant
+// planets on circular orbits are considered, which gets within ~0.001 AU (0.5 s light time).
+pub(crate) fn sun_barycentric_offset(jd: f64) -> DVec3 {
+    let n = jd - J2000;
+    let obliquity = (23.439 - 0.0000004 * n).to_radians();
+    GIANT_PLANETS.iter().map(|&(longitude, rate, radius, mass)| {
+        let longitude = (longitude + rate * n).to_radians();
+        let ecliptic = DVec3::new(longitude.cos(), longitude.sin(), 0.) * radius;
+        let equatorial = DVec3::new(ecliptic.x, ecliptic.y * obliquity.cos(), ecliptic.y * obliquity.sin());
+        -equatorial * mass
+    }).fold(DVec3::ZERO, |sum, v| sum + v)
+}
+
+// Days to add to a (TT) Julian date observed at `observer` to get the barycentric Julian date of
+// a target at `coord`. Only the Roemer delay is included; Shapiro and Einstein delays are below
+// the precision of the low-order ephemeris used here.
+pub fn bjd_correction(coord: &EquatorialCoordinate, jd: f64, observer: &Observer) -> f64 {
+    let ra = coord.right_ascension as f64;
+    let dec = coord.declination as f64;
+    let direction = DVec3::new(dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin());
+    let position = earth_position(jd) + sun_barycentric_offset(jd) + observer.geocentric_position(jd);
+    position.dot(direction) / SPEED_OF_LIGHT
+}
@@ -0,0 +1,205 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use crate::{Band, Distance, Star, StellarPosition};
+
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Ecsv,
+    VoTable
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub enum AngleUnit {
+    #[default]
+    Degrees,
+    Radians
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub enum DistanceUnit {
+    #[default]
+    Parsecs,
+    LightYears
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct ExportOptions {
+    pub(crate) format: ExportFormat,
+    pub(crate) angle_unit: AngleUnit,
+    pub(crate) distance_unit: DistanceUnit
+}
+
+impl ExportOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn format(mut self, format: ExportFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn angle_unit(mut self, unit: AngleUnit) -> Self {
+        self.angle_unit = unit;
+        self
+    }
+
+    pub fn distance_unit(mut self, unit: DistanceUnit) -> Self {
+        self.distance_unit = unit;
+        self
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Kind {
+    Int,
+    Float,
+    Text
+}
+
+// (name, unit, ucd, kind); units follow the VOUnit spelling that astropy and TOPCAT understand.
+fn columns(options: &ExportOptions) -> Vec<(&'static str, &'static str, &'static str, Kind)> {
+    let angle = match options.angle_unit {
+        AngleUnit::Degrees => "deg",
+        AngleUnit::Radians => "rad"
+    };
+    let distance = match options.distance_unit {
+        DistanceUnit::Parsecs => "pc",
+        DistanceUnit::LightYears => "lyr"
+    };
+    let mut columns = vec![
+        ("id", "", "meta.id", Kind::Int),
+        ("identifier", "", "meta.id;meta.main", Kind::Text),
+        ("name", "", "meta.id", Kind::Text),
+        ("ra", angle, "pos.eq.ra;meta.main", Kind::Float),
+        ("dec", angle, "pos.eq.dec;meta.main", Kind::Float),
+        ("distance", distance, "pos.distance", Kind::Float),
+        ("spec_type", "", "src.spType", Kind::Text),
+        ("constellation", "", "meta.id.parent", Kind::Text)
+    ];
+    for band in ["mag_u", "mag_b", "mag_v", "mag_r", "mag_i", "mag_g", "mag_j", "mag_h", "mag_k"] {
+        columns.push((band, "mag", "phot.mag", Kind::Float));
+    }
+    columns
+}
+
+fn row(star: &Star, options: &ExportOptions) -> Vec<String> {
+    let pos = StellarPosition::from(star.pos);
+    let angle = |v: f32| match options.angle_unit {
+        AngleUnit::Degrees => v.to_degrees(),
+        AngleUnit::Radians => v
+    };
+    let distance = Distance::from_light_years(pos.distance);
+    let distance = match options.distance_unit {
+        DistanceUnit::Parsecs => distance.parsecs(),
+        DistanceUnit::LightYears => distance.light_years()
+    };
+    let p = &star.photometry;
+    let mut row = vec![
+        star.id.to_string(),
+        star.identifier.clone(),
+        star.name.clone(),
+        angle(pos.coord.right_ascension).to_string(),
+        angle(pos.coord.declination).to_string(),
+        if distance > 0. { distance.to_string() } else { String::new() },
+        if star.has_spectral_type() { star.class.clone() } else { String::new() },
+        star.constellation.map_or_else(String::new, |c| c.abbreviation().to_string())
+    ];
+    row.extend(Band::ALL.map(|band| p.get(band).map(|m| m.to_string()).unwrap_or_default()));
+    row
+}
+
+pub fn export<P: AsRef<Path>>(stars: &[Star], path: P, options: &ExportOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let mut w = BufWriter::new(File::create(path)?);
+    write_to(stars, &mut w, options)?;
+    w.flush()?;
+    Ok(())
+}
+
+pub fn write_to<W: Write>(stars: &[Star], w: W, options: &ExportOptions) -> Result<(), Box<dyn std::error::Error>> {
+    match options.format {
+        ExportFormat::Csv => write_csv(stars, w, options, false),
+        ExportFormat::Ecsv => write_csv(stars, w, options, true),
+        ExportFormat::VoTable => write_votable(stars, w, options)
+    }
+}
+
+// Plain CSV carries units in the header ("ra [deg]"); ECSV puts them into its YAML preamble instead.
+fn write_csv<W: Write>(stars: &[Star], mut w: W, options: &ExportOptions, ecsv: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let columns = columns(options);
+    if ecsv {
+        writeln!(w, "# %ECSV 1.0")?;
+        writeln!(w, "# ---")?;
+        writeln!(w, "# delimiter: ','")?;
+        writeln!(w, "# datatype:")?;
+        for (name, unit, _, kind) in &columns {
+            let datatype = match kind {
+                Kind::Int => "int64",
+                Kind::Float => "float64",
+                Kind::Text => "string"
+            };
+            if unit.is_empty() {
+                writeln!(w, "# - {{name: {}, datatype: {}}}", name, datatype)?;
+            } else {
+                writeln!(w, "# - {{name: {}, unit: {}, datatype: {}}}", name, unit, datatype)?;
+            }
+        }
+        writeln!(w, "# schema: astropy-2.0")?;
+    }
+    let mut wtr = csv::Writer::from_writer(w);
+    wtr.write_record(columns.iter().map(|(name, unit, _, _)| if ecsv || unit.is_empty() { name.to_string() } else { format!("{} [{}]", name, unit) }))?;
+    for star in stars {
+        wtr.write_record(row(star, options))?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+fn write_votable<W: Write>(stars: &[Star], mut w: W, options: &ExportOptions) -> Result<(), Box<dyn std::error::Error>> {
+    writeln!(w, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(w, "<VOTABLE version=\"1.4\" xmlns=\"http://www.ivoa.net/xml/VOTable/v1.3\">")?;
+    writeln!(w, "<RESOURCE>\n<TABLE>")?;
+    for (name, unit, ucd, kind) in columns(options) {
+        let (datatype, arraysize) = match kind {
+            Kind::Int => ("long", ""),
+            Kind::Float => ("double", ""),
+            Kind::Text => ("char", " arraysize=\"*\"")
+        };
+        let unit = if unit.is_empty() { String::new() } else { format!(" unit=\"{}\"", unit) };
+        writeln!(w, "<FIELD name=\"{}\" datatype=\"{}\"{}{} ucd=\"{}\"/>", name, datatype, arraysize, unit, ucd)?;
+    }
+    writeln!(w, "<DATA>\n<TABLEDATA>")?;
+    for star in stars {
+        write!(w, "<TR>")?;
+        for value in row(star, options) {
+            write!(w, "<TD>{}</TD>", xml_escape(&value))?;
+        }
+        writeln!(w, "</TR>")?;
+    }
+    writeln!(w, "</TABLEDATA>\n</DATA>\n</TABLE>\n</RESOURCE>\n</VOTABLE>")?;
+    Ok(())
+}
+
+pub(crate) fn xml_escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{assert_golden, SimbadCsv};
+
+    #[test]
+    fn votable_matches_golden() {
+        let path = SimbadCsv::new(crate::tests::sample_records()).write_temp("votable-source.csv").unwrap();
+        let stars = crate::import(&path).unwrap();
+        let mut out = vec![];
+        write_to(&stars, &mut out, &ExportOptions::new().format(ExportFormat::VoTable)).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("datatype=\"char\" arraysize=\"*\""));
+        assert_golden(crate::tests::golden("export.vot"), &out);
+    }
+}
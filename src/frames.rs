@@ -0,0 +1,153 @@
+use glam::Vec3;
+use crate::{Angle, EquatorialCoordinate, StellarPosition};
+
+// ICRS -> galactic rotation (Hipparcos definition).
+pub(crate) const EQUATORIAL_TO_GALACTIC: [[f64; 3]; 3] = [
+    [-0.0548755604, -0.8734370902, -0.4838350155],
+    [0.4941094279, -0.4448296300, 0.7469822445],
+    [-0.8676661490, -0.1980763734, 0.4559837762]
+];
+
+// Galactic -> supergalactic rotation (de Vaucouleurs; pole at l = 47.37°, b = +6.32°, zero
+// point at l = 137.37°, b = 0°).
+const GALACTIC_TO_SUPERGALACTIC: [[f64; 3]; 3] = [
+    [-0.7357425748, 0.6772612964, 0.0],
+    [-0.0745537784, -0.0809914713, 0.9939225904],
+    [0.6731453021, 0.7312711658, 0.1100812622]
+];
+
+// Product of the two rotations above.
+const EQUATORIAL_TO_SUPERGALACTIC: [[f64; 3]; 3] = [
+    [0.3750154778, 0.3413589618, 0.8618801834],
+    [-0.8983204556, -0.0957271407, 0.4287851135],
+    [0.2288749668, -0.9350456533, 0.2707505787]
+];
+
+// FK4 B1950 -> FK5 J2000 rotation for positions (Standish 1982, the position block of the
+// Aoki et al. transformation).
+const FK4_TO_FK5: [[f64; 3]; 3] = [
+    [0.9999256782, -0.0111820611, -0.0048579477],
+    [0.0111820610, 0.9999374784, -0.0000271765],
+    [0.0048579479, -0.0000271474, 0.9999881997]
+];
+
+// Elliptic aberration (E-terms) folded into FK4 catalogue positions, in radians.
+const FK4_E_TERMS: [f64; 3] = [-1.62557e-6, -0.31919e-6, -0.13843e-6];
+
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub struct GalacticCoordinate {
+    pub longitude: f32,
+    pub latitude: f32
+}
+
+impl GalacticCoordinate {
+    pub fn new(longitude: f32, latitude: f32) -> Self {
+        Self {
+            longitude: longitude.wrap_positive(),
+            latitude: latitude.clamp(-90f32.to_radians(), 90f32.to_radians()),
+        }
+    }
+}
+
+impl From<EquatorialCoordinate> for GalacticCoordinate {
+    fn from(coord: EquatorialCoordinate) -> Self {
+        let (lon, lat) = rotate(&EQUATORIAL_TO_GALACTIC, coord.right_ascension, coord.declination, false);
+        Self::new(lon, lat)
+    }
+}
+
+impl From<GalacticCoordinate> for EquatorialCoordinate {
+    fn from(coord: GalacticCoordinate) -> Self {
+        let (ra, dec) = rotate(&EQUATORIAL_TO_GALACTIC, coord.longitude, coord.latitude, true);
+        Self::new(ra, dec)
+    }
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub struct SupergalacticCoordinate {
+    pub longitude: f32,
+    pub latitude: f32
+}
+
+impl SupergalacticCoordinate {
+    pub fn new(longitude: f32, latitude: f32) -> Self {
+        Self {
+            longitude: longitude.wrap_positive(),
+            latitude: latitude.clamp(-90f32.to_radians(), 90f32.to_radians()),
+        }
+    }
+}
+
+impl From<EquatorialCoordinate> for SupergalacticCoordinate {
+    fn from(coord: EquatorialCoordinate) -> Self {
+        let (lon, lat) = rotate(&EQUATORIAL_TO_SUPERGALACTIC, coord.right_ascension, coord.declination, false);
+        Self::new(lon, lat)
+    }
+}
+
+impl From<SupergalacticCoordinate> for EquatorialCoordinate {
+    fn from(coord: SupergalacticCoordinate) -> Self {
+        let (ra, dec) = rotate(&EQUATORIAL_TO_SUPERGALACTIC, coord.longitude, coord.latitude, true);
+        Self::new(ra, dec)
+    }
+}
+
+impl From<GalacticCoordinate> for SupergalacticCoordinate {
+    fn from(coord: GalacticCoordinate) -> Self {
+        let (lon, lat) = rotate(&GALACTIC_TO_SUPERGALACTIC, coord.longitude, coord.latitude, false);
+        Self::new(lon, lat)
+    }
+}
+
+impl From<SupergalacticCoordinate> for GalacticCoordinate {
+    fn from(coord: SupergalacticCoordinate) -> Self {
+        let (l, b) = rotate(&GALACTIC_TO_SUPERGALACTIC, coord.longitude, coord.latitude, true);
+        Self::new(l, b)
+    }
+}
+
+// FK4 B1950 catalogue position (SIMBAD's coord3) to J2000. The E-terms are removed and the frames
+// rotated, but without the proper motion over the 50 years between the epochs, so this is only
+// good to an arcsecond or so for nearby stars.
+pub(crate) fn fk4_to_j2000(coord: &EquatorialCoordinate) -> EquatorialCoordinate {
+    let r = spherical_to_unit(coord.right_ascension as f64, coord.declination as f64);
+    let dot = (0..3).map(|i| r[i] * FK4_E_TERMS[i]).sum::<f64>();
+    let corrected = [0, 1, 2].map(|i| r[i] - FK4_E_TERMS[i] + dot * r[i]);
+    let (ra, dec) = unit_to_spherical(apply(&FK4_TO_FK5, corrected, false));
+    EquatorialCoordinate::new(ra as f32, dec as f32)
+}
+
+// Heliocentric cartesian frame in the position's distance unit: X toward the galactic centre,
+// Y toward l = 90° (direction of rotation), Z toward the north galactic pole.
+impl StellarPosition {
+    pub fn to_galactic_cartesian(&self) -> Vec3 {
+        let v: Vec3 = (*self).into();
+        let [x, y, z] = apply(&EQUATORIAL_TO_GALACTIC, v.to_array().map(f64::from), false);
+        Vec3::new(x as f32, y as f32, z as f32)
+    }
+
+    pub fn from_galactic_cartesian(v: Vec3) -> Self {
+        let [x, y, z] = apply(&EQUATORIAL_TO_GALACTIC, v.to_array().map(f64::from), true);
+        Vec3::new(x as f32, y as f32, z as f32).into()
+    }
+}
+
+pub(crate) fn spherical_to_unit(lon: f64, lat: f64) -> [f64; 3] {
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+pub(crate) fn unit_to_spherical(v: [f64; 3]) -> (f64, f64) {
+    (v[1].atan2(v[0]), v[2].clamp(-1., 1.).asin())
+}
+
+pub(crate) fn rotate(matrix: &[[f64; 3]; 3], lon: f32, lat: f32, inverse: bool) -> (f32, f32) {
+    let rotated = apply(matrix, spherical_to_unit(lon as f64, lat as f64), inverse);
+    let (lon, lat) = unit_to_spherical(rotated);
+    (lon as f32, lat as f32)
+}
+
+// Multiplies by `matrix`, or by its transpose (the inverse rotation) if `inverse` is set.
+pub(crate) fn apply(matrix: &[[f64; 3]; 3], v: [f64; 3], inverse: bool) -> [f64; 3] {
+    let element = |row: usize, col: usize| if inverse { matrix[col][row] } else { matrix[row][col] };
+    [0, 1, 2].map(|row| (0..3).map(|col| element(row, col) * v[col]).sum::<f64>())
+}
@@ -0,0 +1,347 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use crate::export::xml_escape;
+use crate::{Band, Constellation, EquatorialCoordinate, SimbadError, Star, StellarPosition};
+
+// Sky positions as geographic coordinates, for web map libraries (Leaflet, OpenLayers, Aladin
+// Lite overlays, Google Earth's sky mode): latitude is the declination in degrees and longitude
+// derived from the right ascension per `LongitudeConvention`, wrapped to -180..180.
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub enum GeoFormat {
+    #[default]
+    GeoJson,
+    Kml
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LongitudeConvention {
+    // lon = RA, so RA 270° becomes -90°. Matches Aladin Lite; the default for GeoJSON.
+    RightAscension,
+    // lon = RA - 180°, which Google Earth's sky mode and Google Sky expect; the default for KML.
+    GoogleSky,
+    // lon = 180° - RA, i.e. the sky seen from inside with east to the left, as on a star chart.
+    Mirrored
+}
+
+#[derive(Default, Clone, Debug)]
+pub struct GeoOptions {
+    pub(crate) format: GeoFormat,
+    // None picks the convention the format's usual viewer expects.
+    pub(crate) longitude: Option<LongitudeConvention>
+}
+
+impl GeoOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn format(mut self, format: GeoFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn longitude(mut self, convention: LongitudeConvention) -> Self {
+        self.longitude = Some(convention);
+        self
+    }
+
+    pub fn convention(&self) -> LongitudeConvention {
+        self.longitude.unwrap_or(match self.format {
+            GeoFormat::GeoJson => LongitudeConvention::RightAscension,
+            GeoFormat::Kml => LongitudeConvention::GoogleSky
+        })
+    }
+
+    // (longitude, latitude) in degrees.
+    pub fn lon_lat(&self, coord: &EquatorialCoordinate) -> (f64, f64) {
+        let ra = (coord.right_ascension as f64).to_degrees();
+        let lon = match self.convention() {
+            LongitudeConvention::RightAscension => ra,
+            LongitudeConvention::GoogleSky => ra - 180.,
+            LongitudeConvention::Mirrored => 180. - ra
+        };
+        ((lon + 180.).rem_euclid(360.) - 180., (coord.declination as f64).to_degrees())
+    }
+}
+
+// Outline of a constellation as a closed ring of vertices in J2000.
+#[derive(Clone, Debug)]
+pub struct Boundary {
+    pub constellation: Constellation,
+    pub vertices: Vec<EquatorialCoordinate>
+}
+
+// The crate doesn't ship the IAU boundaries; this reads them from the VizieR catalogue VI/49
+// (`bound_20.dat`): one vertex per line as RA in decimal hours, Dec in degrees and the
+// constellation abbreviation, consecutive lines with the same abbreviation forming one boundary.
+// Serpens has two, "SER1" (Caput) and "SER2" (Cauda), on either side of Ophiuchus. Further
+// columns are ignored.
+pub fn read_boundaries<P: AsRef<Path>>(path: P) -> Result<Vec<Boundary>, Box<dyn std::error::Error>> {
+    let mut boundaries: Vec<Boundary> = vec![];
+    let mut previous = String::new();
+    for line in std::fs::read_to_string(path)?.lines() {
+        let tokens = line.split_whitespace().collect::<Vec<_>>();
+        if tokens.is_empty() || tokens[0].starts_with('#') {
+            continue;
+        }
+        let [ra, dec, constellation, ..] = tokens[..] else { return Err(SimbadError::InvalidFormat.into()) };
+        let ra = ra.parse::<f32>().map_err(|_| SimbadError::InvalidCoordinate)?;
+        let dec = dec.parse::<f32>().map_err(|_| SimbadError::InvalidCoordinate)?;
+        let vertex = EquatorialCoordinate::new((ra * 15.).to_radians(), dec.to_radians());
+        match boundaries.last_mut() {
+            Some(boundary) if previous == constellation => boundary.vertices.push(vertex),
+            _ => {
+                let parsed = match constellation {
+                    "SER1" | "SER2" => Constellation::Serpens,
+                    code => code.parse::<Constellation>()?
+                };
+                boundaries.push(Boundary { constellation: parsed, vertices: vec![vertex] });
+                previous = constellation.to_string();
+            }
+        }
+    }
+    Ok(boundaries)
+}
+
+pub fn export_geo<P: AsRef<Path>>(stars: &[Star], boundaries: &[Boundary], path: P, options: &GeoOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let mut w = BufWriter::new(File::create(path)?);
+    write_geo(stars, boundaries, &mut w, options)?;
+    w.flush()?;
+    Ok(())
+}
+
+// Stars become points (with identifier, name, V and spectral type), boundaries polygons. As RFC
+// 7946 §3.1.9 asks, a boundary crossing lon ±180° is cut there into a multi-polygon, so no
+// longitude leaves -180..180; rings are counterclockwise.
+pub fn write_geo<W: Write>(stars: &[Star], boundaries: &[Boundary], w: W, options: &GeoOptions) -> std::io::Result<()> {
+    match options.format {
+        GeoFormat::GeoJson => write_geojson(stars, boundaries, w, options),
+        GeoFormat::Kml => write_kml(stars, boundaries, w, options)
+    }
+}
+
+// Vertices of a boundary, not repeating the first, with longitudes unwrapped so neighbours are
+// never more than 180° apart. The boundaries of UMi and Oct wind once around a pole, leaving the
+// last vertex 360° away from the first; those are closed through the pole.
+fn ring(boundary: &Boundary, options: &GeoOptions) -> Vec<(f64, f64)> {
+    let mut ring: Vec<(f64, f64)> = vec![];
+    for vertex in &boundary.vertices {
+        let (mut lon, lat) = options.lon_lat(vertex);
+        if let Some(&(previous, _)) = ring.last() {
+            lon += ((previous - lon) / 360.).round() * 360.;
+        }
+        ring.push((lon, lat));
+    }
+    if let (Some(&(first, first_lat)), Some(&(last, _))) = (ring.first(), ring.last()) {
+        let closing = first + ((last - first) / 360.).round() * 360.;
+        if closing != first {
+            let pole = if ring.iter().map(|&(_, lat)| lat).sum::<f64>() > 0. { 90. } else { -90. };
+            ring.extend([(closing, first_lat), (closing, pole), (first, pole)]);
+        }
+    }
+    ring
+}
+
+// Parts of an unwrapped ring within each 360° window, moved into -180..180, closed and turned
+// counterclockwise.
+fn split_at_antimeridian(ring: &[(f64, f64)]) -> Vec<Vec<(f64, f64)>> {
+    let window = |lon: f64| ((lon + 180.) / 360.).floor() as i64;
+    let lons = ring.iter().map(|&(lon, _)| lon);
+    let (Some(min), Some(max)) = (lons.clone().reduce(f64::min), lons.reduce(f64::max)) else { return vec![] };
+    let mut parts = vec![];
+    for k in window(min)..=window(max) {
+        let offset = k as f64 * 360.;
+        let mut part = clip(&clip(ring, offset - 180., true), offset + 180., false);
+        let area = signed_area(&part);
+        if part.len() < 3 || area.abs() < 1e-9 {
+            continue;
+        }
+        if area < 0. {
+            part.reverse();
+        }
+        part.iter_mut().for_each(|(lon, _)| *lon -= offset);
+        part.push(part[0]);
+        parts.push(part);
+    }
+    parts
+}
+
+// Sutherland-Hodgman against the half plane lon >= bound (or <= bound), interpolating linearly
+// in lon/lat as the edges are drawn.
+fn clip(ring: &[(f64, f64)], bound: f64, keep_greater: bool) -> Vec<(f64, f64)> {
+    let inside = |(lon, _): (f64, f64)| if keep_greater { lon >= bound } else { lon <= bound };
+    let mut clipped = vec![];
+    for (i, &current) in ring.iter().enumerate() {
+        let previous = ring[(i + ring.len() - 1) % ring.len()];
+        if inside(current) != inside(previous) {
+            let t = (bound - previous.0) / (current.0 - previous.0);
+            clipped.push((bound, previous.1 + t * (current.1 - previous.1)));
+        }
+        if inside(current) {
+            clipped.push(current);
+        }
+    }
+    clipped
+}
+
+// Shoelace formula; positive for counterclockwise rings.
+fn signed_area(ring: &[(f64, f64)]) -> f64 {
+    (0..ring.len()).map(|i| {
+        let ((x0, y0), (x1, y1)) = (ring[i], ring[(i + 1) % ring.len()]);
+        x0 * y1 - x1 * y0
+    }).sum::<f64>() / 2.
+}
+
+fn json_string(value: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c)
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn write_geojson<W: Write>(stars: &[Star], boundaries: &[Boundary], mut w: W, options: &GeoOptions) -> std::io::Result<()> {
+    writeln!(w, "{{\"type\": \"FeatureCollection\", \"features\": [")?;
+    let mut first = true;
+    let mut separator = |w: &mut W| -> std::io::Result<()> {
+        if !std::mem::take(&mut first) {
+            writeln!(w, ",")?;
+        }
+        Ok(())
+    };
+    for star in stars {
+        separator(&mut w)?;
+        let (lon, lat) = options.lon_lat(&StellarPosition::from(star.pos).coord);
+        let v = star.photometry.get(Band::V).filter(|v| v.is_finite()).map_or("null".to_string(), |v| v.to_string());
+        let spec_type = if star.has_spectral_type() { json_string(&star.class) } else { "null".to_string() };
+        write!(
+            w,
+            "{{\"type\": \"Feature\", \"geometry\": {{\"type\": \"Point\", \"coordinates\": [{:.6}, {:.6}]}}, \"properties\": {{\"identifier\": {}, \"name\": {}, \"mag_v\": {}, \"spec_type\": {}}}}}",
+            lon, lat, json_string(&star.identifier), json_string(&star.name), v, spec_type
+        )?;
+    }
+    for boundary in boundaries {
+        separator(&mut w)?;
+        let parts = split_at_antimeridian(&ring(boundary, options)).iter()
+            .map(|part| format!("[[{}]]", part.iter().map(|(lon, lat)| format!("[{:.6}, {:.6}]", lon, lat)).collect::<Vec<_>>().join(", ")))
+            .collect::<Vec<_>>();
+        let geometry = match &parts[..] {
+            [polygon] => format!("{{\"type\": \"Polygon\", \"coordinates\": {}}}", polygon),
+            parts => format!("{{\"type\": \"MultiPolygon\", \"coordinates\": [{}]}}", parts.join(", "))
+        };
+        write!(
+            w,
+            "{{\"type\": \"Feature\", \"geometry\": {}, \"properties\": {{\"constellation\": {}, \"name\": {}}}}}",
+            geometry, json_string(boundary.constellation.abbreviation()), json_string(boundary.constellation.name())
+        )?;
+    }
+    writeln!(w, "\n]}}")
+}
+
+fn write_kml<W: Write>(stars: &[Star], boundaries: &[Boundary], mut w: W, options: &GeoOptions) -> std::io::Result<()> {
+    writeln!(w, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(w, "<kml xmlns=\"http://www.opengis.net/kml/2.2\" hint=\"target=sky\">\n<Document>")?;
+    for star in stars {
+        let (lon, lat) = options.lon_lat(&StellarPosition::from(star.pos).coord);
+        let name = if star.name.is_empty() { &star.identifier } else { &star.name };
+        let v = star.photometry.get(Band::V).map_or(String::new(), |v| format!("V = {} ", v));
+        writeln!(
+            w,
+            "<Placemark><name>{}</name><description>{}{}</description><Point><coordinates>{:.6},{:.6}</coordinates></Point></Placemark>",
+            xml_escape(name), xml_escape(&v), xml_escape(if star.has_spectral_type() { &star.class } else { "" }), lon, lat
+        )?;
+    }
+    for boundary in boundaries {
+        let polygons = split_at_antimeridian(&ring(boundary, options)).iter()
+            .map(|part| format!(
+                "<Polygon><outerBoundaryIs><LinearRing><coordinates>{}</coordinates></LinearRing></outerBoundaryIs></Polygon>",
+                part.iter().map(|(lon, lat)| format!("{:.6},{:.6}", lon, lat)).collect::<Vec<_>>().join(" ")
+            ))
+            .collect::<Vec<_>>();
+        let geometry = match &polygons[..] {
+            [polygon] => polygon.clone(),
+            polygons => format!("<MultiGeometry>{}</MultiGeometry>", polygons.concat())
+        };
+        writeln!(w, "<Placemark><name>{}</name>{}</Placemark>", xml_escape(boundary.constellation.name()), geometry)?;
+    }
+    writeln!(w, "</Document>\n</kml>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boundary(constellation: Constellation, vertices: &[(f32, f32)]) -> Boundary {
+        Boundary {
+            constellation,
+            vertices: vertices.iter().map(|&(ra, dec)| EquatorialCoordinate::new(ra.to_radians(), dec.to_radians())).collect()
+        }
+    }
+
+    fn lon_lats(parts: &[Vec<(f64, f64)>]) -> impl Iterator<Item = &(f64, f64)> {
+        parts.iter().flatten()
+    }
+
+    #[test]
+    fn reads_both_parts_of_serpens() {
+        let boundaries = read_boundaries(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/bound_20.dat")).unwrap();
+        let read = boundaries.iter().map(|b| (b.constellation, b.vertices.len())).collect::<Vec<_>>();
+        assert_eq!(read, [(Constellation::Scutum, 4), (Constellation::Serpens, 4), (Constellation::Serpens, 4), (Constellation::Sextans, 4)]);
+        assert!((boundaries[1].vertices[0].right_ascension.to_degrees() - 225.).abs() < 1e-3);
+    }
+
+    #[test]
+    fn kml_defaults_to_google_sky_longitudes() {
+        let coord = EquatorialCoordinate::new(90f32.to_radians(), 0.);
+        assert!((GeoOptions::new().lon_lat(&coord).0 - 90.).abs() < 1e-4);
+        assert!((GeoOptions::new().format(GeoFormat::Kml).lon_lat(&coord).0 + 90.).abs() < 1e-4);
+        let mirrored = GeoOptions::new().format(GeoFormat::Kml).longitude(LongitudeConvention::Mirrored);
+        assert!((mirrored.lon_lat(&coord).0 - 90.).abs() < 1e-4);
+    }
+
+    #[test]
+    fn splits_rings_crossing_the_antimeridian() {
+        let crossing = boundary(Constellation::Leo, &[(170., 0.), (190., 0.), (190., 10.), (170., 10.)]);
+        let parts = split_at_antimeridian(&ring(&crossing, &GeoOptions::new()));
+        assert_eq!(parts.len(), 2);
+        assert!(lon_lats(&parts).all(|&(lon, _)| (-180. ..=180.).contains(&lon)));
+        for part in &parts {
+            assert_eq!(part.first(), part.last());
+            assert!(signed_area(&part[..part.len() - 1]) > 0.);
+        }
+        let total = parts.iter().map(|part| signed_area(&part[..part.len() - 1])).sum::<f64>();
+        assert!((total - 200.).abs() < 1e-2, "{}", total);
+        let inside = boundary(Constellation::Leo, &[(10., 0.), (20., 0.), (20., 10.)]);
+        assert_eq!(split_at_antimeridian(&ring(&inside, &GeoOptions::new())).len(), 1);
+    }
+
+    #[test]
+    fn closes_polar_rings_through_the_pole() {
+        let polar = boundary(Constellation::UrsaMinor, &[(0., 80.), (90., 80.), (180., 80.), (270., 80.)]);
+        let parts = split_at_antimeridian(&ring(&polar, &GeoOptions::new()));
+        assert!(lon_lats(&parts).all(|&(lon, _)| (-180. ..=180.).contains(&lon)));
+        assert!(lon_lats(&parts).any(|&(_, lat)| lat == 90.));
+        let total = parts.iter().map(|part| signed_area(&part[..part.len() - 1])).sum::<f64>();
+        assert!((total - 3600.).abs() < 1e-2, "{}", total);
+        let south = boundary(Constellation::Octans, &[(0., -83.), (270., -83.), (180., -83.), (90., -83.)]);
+        let parts = split_at_antimeridian(&ring(&south, &GeoOptions::new()));
+        assert!(lon_lats(&parts).any(|&(_, lat)| lat == -90.) && lon_lats(&parts).all(|&(_, lat)| lat != 90.));
+    }
+
+    #[test]
+    fn geojson_output_stays_within_the_antimeridian() {
+        let boundaries = [boundary(Constellation::UrsaMinor, &[(0., 80.), (90., 80.), (180., 80.), (270., 80.)])];
+        let mut out = vec![];
+        write_geo(&[], &boundaries, &mut out, &GeoOptions::new()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("\"MultiPolygon\""));
+        assert!(out.split(['[', ',', ']']).filter_map(|n| n.trim().parse::<f64>().ok()).all(|n| n.abs() <= 180.));
+    }
+}
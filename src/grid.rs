@@ -0,0 +1,37 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+use crate::EquatorialCoordinate;
+use crate::frames::GalacticCoordinate;
+
+// Spacing between points along each graticule line.
+const SAMPLE_STEP: f32 = std::f32::consts::PI / 180.;
+
+// Meridians every `spacing_ra` and parallels every `spacing_dec` (radians), as polylines. The poles
+// themselves are left out of the parallels since they would collapse into a single point.
+pub fn equatorial_grid(spacing_ra: f32, spacing_dec: f32) -> Vec<Vec<EquatorialCoordinate>> {
+    graticule(spacing_ra, spacing_dec).into_iter()
+        .map(|line| line.into_iter().map(|(ra, dec)| EquatorialCoordinate::new(ra, dec)).collect())
+        .collect()
+}
+
+// Lines of constant galactic longitude/latitude, converted to equatorial coordinates.
+pub fn galactic_grid(spacing_lon: f32, spacing_lat: f32) -> Vec<Vec<EquatorialCoordinate>> {
+    graticule(spacing_lon, spacing_lat).into_iter()
+        .map(|line| line.into_iter().map(|(lon, lat)| GalacticCoordinate::new(lon, lat).into()).collect())
+        .collect()
+}
+
+fn graticule(spacing_lon: f32, spacing_lat: f32) -> Vec<Vec<(f32, f32)>> {
+    let mut lines = vec![];
+    let lat_samples = (std::f32::consts::PI / SAMPLE_STEP).round() as usize;
+    let lon_samples = (TAU / SAMPLE_STEP).round() as usize;
+    for i in 0..(TAU / spacing_lon).round() as usize {
+        let lon = i as f32 * spacing_lon;
+        lines.push((0..=lat_samples).map(|j| (lon, -FRAC_PI_2 + j as f32 * SAMPLE_STEP)).collect());
+    }
+    let parallels = ((FRAC_PI_2 - 1e-4) / spacing_lat).floor() as i32;
+    for i in -parallels..=parallels {
+        let lat = i as f32 * spacing_lat;
+        lines.push((0..=lon_samples).map(|j| (j as f32 * SAMPLE_STEP, lat)).collect());
+    }
+    lines
+}
@@ -0,0 +1,121 @@
+use std::collections::{BTreeMap, HashMap};
+use std::f64::consts::FRAC_PI_2;
+use crate::{Angle, Catalog, EquatorialCoordinate, StellarPosition};
+
+pub fn npix(nside: u32) -> u64 {
+    12 * nside as u64 * nside as u64
+}
+
+// Pixel index in the NESTED scheme; `nside` has to be a power of two.
+pub fn ang2pix_nest(nside: u32, coord: &EquatorialCoordinate) -> u64 {
+    let nside = nside as i64;
+    let z = (coord.declination as f64).sin();
+    let za = z.abs();
+    let tt = (coord.right_ascension as f64).wrap_positive() / FRAC_PI_2;
+    let (face, ix, iy) = if za <= 2. / 3. {
+        let temp1 = nside as f64 * (0.5 + tt);
+        let temp2 = nside as f64 * z * 0.75;
+        let jp = (temp1 - temp2) as i64;
+        let jm = (temp1 + temp2) as i64;
+        let ifp = jp / nside;
+        let ifm = jm / nside;
+        let face = if ifp == ifm { ifp | 4 } else if ifp < ifm { ifp } else { ifm + 8 };
+        (face, jm & (nside - 1), nside - (jp & (nside - 1)) - 1)
+    } else {
+        let ntt = (tt as i64).min(3);
+        let tp = tt - ntt as f64;
+        let tmp = nside as f64 * (3. * (1. - za)).sqrt();
+        let jp = ((tp * tmp) as i64).min(nside - 1);
+        let jm = (((1. - tp) * tmp) as i64).min(nside - 1);
+        if z >= 0. {
+            (ntt, nside - jm - 1, nside - jp - 1)
+        } else {
+            (ntt + 8, jp, jm)
+        }
+    };
+    face as u64 * (nside * nside) as u64 + spread_bits(ix as u64) + (spread_bits(iy as u64) << 1)
+}
+
+// Interleaves the lower 32 bits of `v` with zeros (Morton order).
+pub(crate) fn spread_bits(v: u64) -> u64 {
+    let mut v = v & 0xffff_ffff;
+    v = (v | (v << 16)) & 0x0000_ffff_0000_ffff;
+    v = (v | (v << 8)) & 0x00ff_00ff_00ff_00ff;
+    v = (v | (v << 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+    v = (v | (v << 2)) & 0x3333_3333_3333_3333;
+    (v | (v << 1)) & 0x5555_5555_5555_5555
+}
+
+// Entries bucketed by NESTED pixel that can be updated in place. Entries are identified by a
+// caller-chosen `usize`, e.g. the position in `Catalog::stars` or `Star::id`.
+#[derive(Clone, Debug)]
+pub struct HealpixIndex {
+    nside: u32,
+    cells: BTreeMap<u64, Vec<usize>>,
+    pixels: HashMap<usize, u64>
+}
+
+impl HealpixIndex {
+    // `nside` has to be a power of two.
+    pub fn new(nside: u32) -> Self {
+        Self { nside, cells: BTreeMap::new(), pixels: HashMap::new() }
+    }
+
+    // Indexes every star by its position in `catalog.stars`.
+    pub fn build(catalog: &Catalog, nside: u32) -> Self {
+        let mut index = Self::new(nside);
+        for (i, star) in catalog.stars.iter().enumerate() {
+            index.insert(i, &StellarPosition::from(star.pos).coord);
+        }
+        index
+    }
+
+    pub fn nside(&self) -> u32 {
+        self.nside
+    }
+
+    pub fn len(&self) -> usize {
+        self.pixels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pixels.is_empty()
+    }
+
+    // Inserting an id that is already present moves it.
+    pub fn insert(&mut self, id: usize, coord: &EquatorialCoordinate) {
+        self.remove(id);
+        let pixel = ang2pix_nest(self.nside, coord);
+        self.cells.entry(pixel).or_default().push(id);
+        self.pixels.insert(id, pixel);
+    }
+
+    pub fn remove(&mut self, id: usize) -> bool {
+        let Some(pixel) = self.pixels.remove(&id) else { return false };
+        if let Some(cell) = self.cells.get_mut(&pixel) {
+            cell.retain(|other| *other != id);
+            if cell.is_empty() {
+                self.cells.remove(&pixel);
+            }
+        }
+        true
+    }
+
+    pub fn pixel_of(&self, id: usize) -> Option<u64> {
+        self.pixels.get(&id).copied()
+    }
+
+    pub fn cell(&self, pixel: u64) -> &[usize] {
+        self.cells.get(&pixel).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn cell_at(&self, coord: &EquatorialCoordinate) -> &[usize] {
+        self.cell(ang2pix_nest(self.nside, coord))
+    }
+
+    // Everything inside `pixel` of a coarser `nside`; nested children of a pixel are contiguous.
+    pub fn within(&self, pixel: u64, nside: u32) -> impl Iterator<Item = usize> + '_ {
+        let shift = 2 * (self.nside / nside.clamp(1, self.nside)).trailing_zeros();
+        self.cells.range(pixel << shift..(pixel + 1) << shift).flat_map(|(_, ids)| ids.iter().copied())
+    }
+}
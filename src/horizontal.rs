@@ -0,0 +1,124 @@
+use crate::{ephemeris, Angle, EquatorialCoordinate, Observer};
+use crate::observer::SIDEREAL_RATE;
+
+// Altitude above the horizon and azimuth measured from north through east, both in radians.
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub struct HorizontalCoordinate {
+    pub altitude: f32,
+    pub azimuth: f32
+}
+
+// Refraction lifts objects near the horizon by up to ~0.6°. Off by default, so `to_horizontal`
+// stays purely geometric.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HorizontalOptions {
+    pub(crate) refraction: bool,
+    // °C and hPa at the observer.
+    pub(crate) temperature: f32,
+    pub(crate) pressure: f32
+}
+
+impl Default for HorizontalOptions {
+    fn default() -> Self {
+        Self { refraction: false, temperature: 10., pressure: 1010. }
+    }
+}
+
+impl HorizontalOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn refraction(mut self, refraction: bool) -> Self {
+        self.refraction = refraction;
+        self
+    }
+
+    pub fn temperature(mut self, celsius: f32) -> Self {
+        self.temperature = celsius;
+        self
+    }
+
+    pub fn pressure(mut self, hpa: f32) -> Self {
+        self.pressure = hpa;
+        self
+    }
+
+    // Both formulas are for 10 °C and 1010 hPa; refraction scales with air density.
+    fn density_factor(&self) -> f32 {
+        (self.pressure / 1010.) * (283. / (273. + self.temperature))
+    }
+
+    // Sæmundsson (1986): apparent altitude for a geometric (true) altitude, radians. Altitudes
+    // more than a degree below the horizon get the refraction at -1°.
+    pub fn apparent_altitude(&self, altitude: f32) -> f32 {
+        if !self.refraction {
+            return altitude;
+        }
+        let h = altitude.to_degrees().max(-1.);
+        let arcmin = 1.02 / (h + 10.3 / (h + 5.11)).to_radians().tan();
+        altitude + (arcmin * self.density_factor() / 60.).to_radians()
+    }
+
+    // Bennett (1982): geometric altitude for an observed (apparent) altitude, radians.
+    pub fn true_altitude(&self, apparent: f32) -> f32 {
+        if !self.refraction {
+            return apparent;
+        }
+        let h = apparent.to_degrees().max(-1.);
+        let arcmin = 1. / (h + 7.31 / (h + 4.4)).to_radians().tan();
+        apparent - (arcmin * self.density_factor() / 60.).to_radians()
+    }
+}
+
+impl EquatorialCoordinate {
+    pub fn hour_angle_at(&self, observer: &Observer, jd: f64) -> f64 {
+        (observer.local_sidereal_time(jd) - self.right_ascension as f64).wrap_signed()
+    }
+
+    pub fn to_horizontal(&self, observer: &Observer, jd: f64) -> HorizontalCoordinate {
+        let hour_angle = self.hour_angle_at(observer, jd);
+        let (sin_lat, cos_lat) = (observer.latitude as f64).sin_cos();
+        let (sin_dec, cos_dec) = (self.declination as f64).sin_cos();
+        let (sin_h, cos_h) = hour_angle.sin_cos();
+        let altitude = (sin_lat * sin_dec + cos_lat * cos_dec * cos_h).clamp(-1., 1.).asin();
+        let azimuth = (-cos_dec * sin_h).atan2(sin_dec * cos_lat - cos_dec * sin_lat * cos_h).wrap_positive();
+        HorizontalCoordinate {
+            altitude: altitude as f32,
+            azimuth: azimuth as f32,
+        }
+    }
+
+    pub fn to_horizontal_with(&self, observer: &Observer, jd: f64, options: &HorizontalOptions) -> HorizontalCoordinate {
+        let horizontal = self.to_horizontal(observer, jd);
+        HorizontalCoordinate {
+            altitude: options.apparent_altitude(horizontal.altitude),
+            ..horizontal
+        }
+    }
+
+    // Julian date of the next upper culmination at or after `jd`.
+    pub fn transit_after(&self, observer: &Observer, jd: f64) -> f64 {
+        jd + (-self.hour_angle_at(observer, jd)).wrap_positive() / SIDEREAL_RATE
+    }
+
+    pub fn culmination_altitude(&self, observer: &Observer) -> f32 {
+        std::f32::consts::FRAC_PI_2 - (observer.latitude - self.declination).abs()
+    }
+
+    // Rise and set around the given transit for a horizon at `altitude`. None if the object never
+    // crosses that altitude (circumpolar or never rising).
+    pub fn rise_set(&self, observer: &Observer, transit: f64, altitude: f32) -> Option<(f64, f64)> {
+        let (sin_lat, cos_lat) = (observer.latitude as f64).sin_cos();
+        let (sin_dec, cos_dec) = (self.declination as f64).sin_cos();
+        let cos_h0 = ((altitude as f64).sin() - sin_lat * sin_dec) / (cos_lat * cos_dec);
+        if !(-1. ..=1.).contains(&cos_h0) { return None; }
+        let half_arc = cos_h0.acos() / SIDEREAL_RATE;
+        Some((transit - half_arc, transit + half_arc))
+    }
+}
+
+pub(crate) fn sun_coordinate(jd: f64) -> EquatorialCoordinate {
+    let sun = ephemeris::sun_position(jd).normalize();
+    EquatorialCoordinate::new(sun.y.atan2(sun.x) as f32, sun.z.asin() as f32)
+}
@@ -0,0 +1,121 @@
+use std::fmt::{Display, Formatter};
+
+// Splits a trailing multiple-system component ("alf Cen A", "* 61 Cyg B", "HD 1234 Ab") off an
+// identifier. Only a separate token of one uppercase letter A-H, optionally followed by a second
+// component letter, counts, so names that merely end in "B" are left alone.
+pub fn split_component(identifier: &str) -> (&str, Option<&str>) {
+    let identifier = identifier.trim();
+    let Some((base, last)) = identifier.rsplit_once(char::is_whitespace) else { return (identifier, None) };
+    let mut chars = last.chars();
+    let is_component = match (chars.next(), chars.next(), chars.next()) {
+        (Some(first), second, None) => ('A'..='H').contains(&first) && second.is_none_or(|c| ('A'..='H').contains(&c) || ('a'..='h').contains(&c)),
+        _ => false
+    };
+    if is_component && !base.trim().is_empty() && !base.trim().ends_with('*') {
+        (base.trim_end(), Some(last))
+    } else {
+        (identifier, None)
+    }
+}
+
+pub fn is_primary_component(component: &str) -> bool {
+    component.starts_with('A')
+}
+
+// A SIMBAD identifier with internal whitespace collapsed, e.g. "HD   48915" -> "HD 48915".
+#[cfg_attr(feature = "schemars", derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema))]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Identifier(String);
+
+impl Identifier {
+    pub fn new(identifier: &str) -> Self {
+        Self(normalize_whitespace(identifier))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn matches(&self, query: &str) -> bool {
+        names_match(&self.0, query)
+    }
+
+    // Designation within `catalog`, e.g. "1234" for "Gaia DR3 1234" and catalog "Gaia DR3".
+    pub fn in_catalog(&self, catalog: &str) -> Option<&str> {
+        designation_in(&self.0, catalog)
+    }
+}
+
+impl Display for Identifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for Identifier {
+    fn from(identifier: &str) -> Self {
+        Self::new(identifier)
+    }
+}
+
+// Case-insensitive on the catalog name, which has to be followed by whitespace.
+pub fn designation_in<'a>(identifier: &'a str, catalog: &str) -> Option<&'a str> {
+    let catalog = normalize_whitespace(catalog);
+    let prefix = identifier.get(..catalog.len()).filter(|prefix| prefix.eq_ignore_ascii_case(&catalog))?;
+    let rest = &identifier[prefix.len()..];
+    rest.starts_with(char::is_whitespace).then(|| rest.trim()).filter(|rest| !rest.is_empty())
+}
+
+pub fn normalize_whitespace(identifier: &str) -> String {
+    identifier.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Splits a "|" separated alias list, dropping empty entries.
+pub fn split_aliases(list: &str) -> Vec<Identifier> {
+    list.split('|').map(Identifier::new).filter(|id| !id.0.is_empty()).collect()
+}
+
+// Case-insensitive comparison that ignores all whitespace, so "hd48915" matches "HD   48915".
+pub fn names_match(a: &str, b: &str) -> bool {
+    fold(a) == fold(b)
+}
+
+pub(crate) fn fold(s: &str) -> Vec<char> {
+    s.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect()
+}
+
+pub(crate) fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+// Stable reference to a star across exports, derived from its primary identifier: lowercase with
+// all whitespace removed, so "HD 48915" and "hd48915" give the same key. Unlike `Star.id` it
+// doesn't depend on the row order of the export.
+#[derive(Default, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StarKey(String);
+
+impl StarKey {
+    pub fn from_identifier(identifier: &str) -> Self {
+        Self(fold(identifier).into_iter().collect())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for StarKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
@@ -0,0 +1,182 @@
+use glam::DVec3;
+use crate::motion::KM_S_PER_AU_YR;
+use crate::{Distance, EquatorialCoordinate, Photometry, ProperMotion, Star, StellarPosition};
+use crate::frames::{apply, spherical_to_unit, EQUATORIAL_TO_GALACTIC};
+
+// Light-years per year at 1 km/s.
+const LY_PER_YEAR_PER_KM_S: f64 = 1. / 299_792.458;
+
+// Sun's velocity relative to the Local Standard of Rest in km/s; U toward the galactic centre,
+// V in the direction of rotation, W toward the north galactic pole.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SolarMotion {
+    pub u: f32,
+    pub v: f32,
+    pub w: f32
+}
+
+impl SolarMotion {
+    // Schönrich, Binney & Dehnen (2010).
+    pub const SCHOENRICH_2010: Self = Self { u: 11.1, v: 12.24, w: 7.25 };
+    // Dehnen & Binney (1998).
+    pub const DEHNEN_BINNEY_1998: Self = Self { u: 10.0, v: 5.25, w: 7.17 };
+
+    pub fn new(u: f32, v: f32, w: f32) -> Self {
+        Self { u, v, w }
+    }
+}
+
+impl Default for SolarMotion {
+    fn default() -> Self {
+        Self::SCHOENRICH_2010
+    }
+}
+
+// Galactic space velocity in km/s, same axes as `SolarMotion`.
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub struct Uvw {
+    pub u: f32,
+    pub v: f32,
+    pub w: f32
+}
+
+impl Uvw {
+    // Heliocentric UVW from position, distance, proper motion (mas/yr) and radial velocity (km/s).
+    pub fn from_heliocentric(coord: &EquatorialCoordinate, distance: Distance, proper_motion: &ProperMotion, radial_velocity: f32) -> Self {
+        let velocity = space_velocity(coord, distance, proper_motion, radial_velocity).to_array();
+        let [u, v, w] = apply(&EQUATORIAL_TO_GALACTIC, velocity, false);
+        Self { u: u as f32, v: v as f32, w: w as f32 }
+    }
+
+    // Adds the solar motion, giving the velocity relative to the LSR.
+    pub fn to_lsr(&self, solar_motion: &SolarMotion) -> Self {
+        Self {
+            u: self.u + solar_motion.u,
+            v: self.v + solar_motion.v,
+            w: self.w + solar_motion.w,
+        }
+    }
+
+    pub fn speed(&self) -> f32 {
+        (self.u * self.u + self.v * self.v + self.w * self.w).sqrt()
+    }
+}
+
+// Heliocentric radial velocity (km/s) of a source at `coord` converted to the LSR frame.
+pub fn radial_velocity_to_lsr(radial_velocity: f32, coord: &EquatorialCoordinate, solar_motion: &SolarMotion) -> f32 {
+    let equatorial = spherical_to_unit(coord.right_ascension as f64, coord.declination as f64);
+    let [x, y, z] = apply(&EQUATORIAL_TO_GALACTIC, equatorial, false);
+    radial_velocity + (solar_motion.u as f64 * x + solar_motion.v as f64 * y + solar_motion.w as f64 * z) as f32
+}
+
+// Heliocentric velocity in equatorial cartesian axes, km/s.
+pub(crate) fn space_velocity(coord: &EquatorialCoordinate, distance: Distance, proper_motion: &ProperMotion, radial_velocity: f32) -> DVec3 {
+    let (radial, east, north) = local_axes(coord);
+    let tangential = KM_S_PER_AU_YR * distance.parsecs() as f64 / 1000.;
+    radial * radial_velocity as f64 + east * (proper_motion.ra as f64 * tangential) + north * (proper_motion.dec as f64 * tangential)
+}
+
+// Unit vectors toward the source, east and north.
+fn local_axes(coord: &EquatorialCoordinate) -> (DVec3, DVec3, DVec3) {
+    let (sin_ra, cos_ra) = (coord.right_ascension as f64).sin_cos();
+    let (sin_dec, cos_dec) = (coord.declination as f64).sin_cos();
+    (
+        DVec3::new(cos_dec * cos_ra, cos_dec * sin_ra, sin_dec),
+        DVec3::new(-sin_ra, cos_ra, 0.),
+        DVec3::new(-sin_dec * cos_ra, -sin_dec * sin_ra, cos_dec)
+    )
+}
+
+impl Star {
+    // Moves the star along a straight line in space for `years`, using proper motion, distance
+    // and radial velocity (0 if unknown). Proper motion, radial velocity and magnitudes are
+    // updated for the new position. Without proper motion or distance only the epoch changes.
+    pub fn propagate(&mut self, years: f64) {
+        let position = StellarPosition::from(self.pos);
+        if let Some(pm) = self.proper_motion.filter(|_| position.distance > 0.) {
+            let distance = Distance::from_light_years(position.distance);
+            let velocity = space_velocity(&position.coord, distance, &pm, self.radial_velocity.unwrap_or(0.));
+            let moved = (self.pos.as_dvec3() + velocity * years * LY_PER_YEAR_PER_KM_S).as_vec3();
+            let moved_position = StellarPosition::from(moved);
+            let (radial, east, north) = local_axes(&moved_position.coord);
+            let tangential = KM_S_PER_AU_YR * Distance::from_light_years(moved_position.distance).parsecs() as f64 / 1000.;
+            self.proper_motion = Some(ProperMotion::new((velocity.dot(east) / tangential) as f32, (velocity.dot(north) / tangential) as f32));
+            if self.radial_velocity.is_some() {
+                self.radial_velocity = Some(velocity.dot(radial) as f32);
+            }
+            self.photometry = self.photometry.dimmed(5. * (moved_position.distance / position.distance).log10());
+            self.pos = moved;
+        }
+        self.epoch += years * 365.25;
+    }
+}
+
+impl Photometry {
+    fn dimmed(&self, delta: f32) -> Photometry {
+        self.map(|_, m| m + delta)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MovingGroup {
+    Hyades,
+    PleiadesStream,
+    BetaPictoris
+}
+
+impl MovingGroup {
+    pub const ALL: [MovingGroup; 3] = [Self::Hyades, Self::PleiadesStream, Self::BetaPictoris];
+
+    // Mean heliocentric UVW and the velocity ellipsoid's per-axis dispersion, km/s.
+    pub fn ellipsoid(&self) -> (Uvw, Uvw) {
+        let (mean, sigma) = match self {
+            Self::Hyades => ([-41.1, -19.2, -1.4], [4.0, 3.0, 3.0]),
+            Self::PleiadesStream => ([-11.6, -21.0, -11.4], [6.0, 5.0, 6.0]),
+            Self::BetaPictoris => ([-10.9, -16.0, -9.0], [2.2, 1.5, 1.5])
+        };
+        (Uvw { u: mean[0], v: mean[1], w: mean[2] }, Uvw { u: sigma[0], v: sigma[1], w: sigma[2] })
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MovingGroupMatch {
+    pub group: MovingGroup,
+    // Chance of a member being at least this far from the group mean (χ² with 3 degrees of
+    // freedom on the normalized velocity offset); not a Bayesian membership probability.
+    pub probability: f32
+}
+
+impl Star {
+    // Needs proper motion, radial velocity and a distance.
+    pub fn uvw(&self) -> Option<Uvw> {
+        let position = StellarPosition::from(self.pos);
+        if position.distance <= 0. {
+            return None;
+        }
+        Some(Uvw::from_heliocentric(&position.coord, Distance::from_light_years(position.distance), &self.proper_motion?, self.radial_velocity?))
+    }
+}
+
+// Most likely group first. None if the star's UVW can't be computed.
+pub fn classify_moving_group(star: &Star) -> Option<Vec<MovingGroupMatch>> {
+    let uvw = star.uvw()?;
+    let mut matches = MovingGroup::ALL.iter().map(|&group| {
+        let (mean, sigma) = group.ellipsoid();
+        let chi2 = ((uvw.u - mean.u) / sigma.u).powi(2) + ((uvw.v - mean.v) / sigma.v).powi(2) + ((uvw.w - mean.w) / sigma.w).powi(2);
+        MovingGroupMatch { group, probability: chi2_survival_3(chi2 as f64) as f32 }
+    }).collect::<Vec<_>>();
+    matches.sort_by(|a, b| b.probability.total_cmp(&a.probability));
+    Some(matches)
+}
+
+// P(χ² > x) for three degrees of freedom.
+fn chi2_survival_3(x: f64) -> f64 {
+    erfc((x / 2.).sqrt()) + (2. * x / std::f64::consts::PI).sqrt() * (-x / 2.).exp()
+}
+
+// Abramowitz & Stegun 7.1.26, absolute error below 1.5e-7.
+fn erfc(x: f64) -> f64 {
+    let t = 1. / (1. + 0.3275911 * x);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    poly * (-x * x).exp()
+}
@@ -1,16 +1,271 @@
+//! Reading SIMBAD exports into positioned stars and catalogs.
+//!
+//! No public parser panics on malformed input: coordinate, angle and proper motion parsing,
+//! spectral types, constellations, designations and identifiers, manifests, the CSV importers,
+//! `RecordFile` and `Catalog::load_binary` all report bad data as an error, a skipped row or
+//! `None`. The targets in `fuzz/` exercise this.
+
+pub mod analysis;
+mod angle;
+mod binary;
+mod bright_stars;
+mod catalog;
+pub mod columnar;
+pub mod constellation;
+mod cosmology;
+mod crossmatch;
+mod de;
+pub mod density;
+pub mod designation;
+mod diff;
+pub mod directions;
+mod distance;
+mod ephemeris;
+pub mod export;
+pub mod frames;
+pub mod geo;
+pub mod grid;
+mod motion;
+pub mod healpix;
+mod horizontal;
+pub mod identifier;
+pub mod kinematics;
+pub mod lod;
+mod manifest;
+pub mod observer;
+pub mod observing;
+mod objects;
+pub mod packed;
+pub mod photometry;
+mod options;
+mod parse;
+mod preamble;
+mod schema;
+pub mod projection;
+pub mod provenance;
+mod record_file;
+pub mod render;
+mod separation;
+mod slim;
+mod shared;
+mod validation;
+mod variability;
+mod view;
+pub mod visibility;
+#[cfg(feature = "uom")]
+pub mod units;
+pub mod spectral;
+pub mod synthetic;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod uncertainty;
+mod zones;
+
+use std::collections::HashMap;
 use std::fmt::{Debug, Display, Formatter};
 use std::path::Path;
-use csv::ReaderBuilder;
-use glam::{Vec2, Vec3};
+use std::str::FromStr;
+use csv::{ReaderBuilder, WriterBuilder};
+use glam::{DVec3, Vec2, Vec3};
 use serde::{Deserialize, Serialize};
+use schema::ResolvedHeader;
+pub use angle::Angle;
+pub use bright_stars::bright_stars;
+pub use catalog::{Catalog, CatalogStats, DedupPolicy, MatchKind, NameMatch, Pick, Region, RepresentativeStrategy, SortKey, Summary};
+pub use columnar::ColumnarCatalog;
+pub use constellation::Constellation;
+pub use cosmology::Cosmology;
+pub use crossmatch::{crossmatch_epochs, CrossMatch};
+pub use frames::{GalacticCoordinate, SupergalacticCoordinate};
+pub use horizontal::{HorizontalCoordinate, HorizontalOptions};
+pub use identifier::{Identifier, StarKey};
+pub use diff::{CatalogDiff, Change, DiffTolerance, StarChange};
+pub use designation::DisplayNameStyle;
+pub use distance::{distance_from_parallax, distance_from_parallax_with_prior, Distance, DistanceEstimate, DistancePrior, ParallaxError};
+pub use ephemeris::bjd_correction;
+pub use manifest::{manifest_path, ManifestCheck, ManifestIssue, QueryManifest};
+pub use motion::ProperMotion;
+pub use objects::{import_objects, import_objects_with_options, AstronomicalObject, ExtendedObject, ObjectKind, Photometric, Positioned};
+pub use observer::Observer;
+pub use options::{ColumnSet, ComponentPolicy, ImportOptions, ImportWarning, NumberFormat, ParallaxUnit};
+pub use photometry::{Band, ColorIndex, ZeroPoints};
+pub use record_file::RecordFile;
+pub use schema::RecordSchema;
+pub use uncertainty::{match_significance, CoordinateUncertainty, MATCH_SIGMA, STORAGE_PRECISION_MAS};
+pub use provenance::{Provenance, Source};
+pub use parse::{format_epoch, format_sexagesimal, parse_decimal_degrees, parse_equatorial, parse_sexagesimal, CoordinateFrame, CoordParseError, DisplayStyle, RaUnit};
+pub use separation::separations_batch;
+pub use slim::SlimRecord;
+pub use shared::SharedCatalog;
+pub use spectral::{estimate_distance_spectroscopic, SpectralType, UNKNOWN_SPECTRAL_TYPE};
+pub use validation::{validate, ValidationIssue, ValidationIssueKind};
+pub use variability::Variability;
+pub use view::{scan_records, RecordLayout, RecordView};
+pub use visibility::{coverage, visible_tonight, SkyCoverage, VisibleStar};
+pub use zones::ZoneIndex;
+
+pub const J2000: f64 = ephemeris::J2000;
 
-#[derive(Default, Clone, Debug)]
+#[cfg_attr(feature = "schemars", derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema))]
+#[derive(Clone, Debug)]
 pub struct Star {
     pub id: usize,
+    // Serialized as [x, y, z].
+    #[cfg_attr(feature = "schemars", schemars(with = "[f32; 3]"))]
     pub pos: Vec3,
+    pub coord_uncertainty: Option<CoordinateUncertainty>,
+    pub identifier: String,
+    pub aliases: Vec<Identifier>,
     pub name: String,
     pub class: String,
-    pub constellation: String
+    pub constellation: Option<Constellation>,
+    pub photometry: Photometry,
+    pub proper_motion: Option<ProperMotion>,
+    pub redshift: Option<f32>,
+    // Heliocentric, km/s.
+    pub radial_velocity: Option<f32>,
+    pub variability: Option<Variability>,
+    pub epoch: f64,
+    pub provenance: Vec<Provenance>
+}
+
+impl Default for Star {
+    fn default() -> Self {
+        Self {
+            id: 0,
+            pos: Vec3::ZERO,
+            coord_uncertainty: None,
+            identifier: String::new(),
+            aliases: Vec::new(),
+            name: String::new(),
+            class: String::new(),
+            constellation: None,
+            photometry: Photometry::default(),
+            proper_motion: None,
+            redshift: None,
+            radial_velocity: None,
+            variability: None,
+            epoch: J2000,
+            provenance: Vec::new(),
+        }
+    }
+}
+
+impl Star {
+    pub fn from_radec_str(name: &str, radec: &str, distance_pc: f32) -> Result<Self, SimbadError> {
+        let coord = parse_sexagesimal(radec).map_err(|_| SimbadError::InvalidCoordinate)?;
+        let distance = Distance::from_parsecs(distance_pc).light_years();
+        Ok(Self {
+            pos: StellarPosition::new(distance, coord.right_ascension, coord.declination).into(),
+            name: name.to_string(),
+            ..Default::default()
+        })
+    }
+
+    // Keyed on the identifier, or the proper name for stars without one (e.g. `bright_stars`). None
+    // when both are empty: such stars can't be told apart by name, so they are never merged or
+    // deduplicated by key.
+    pub fn key(&self) -> Option<StarKey> {
+        let key = StarKey::from_identifier(if self.identifier.trim().is_empty() { &self.name } else { &self.identifier });
+        (!key.as_str().is_empty()).then_some(key)
+    }
+
+    // Cross-identification in another catalog, from the identifier or the aliases, e.g.
+    // `id_in_catalog("Gaia DR3")` or `id_in_catalog("2MASS")`.
+    pub fn id_in_catalog(&self, catalog: &str) -> Option<&str> {
+        identifier::designation_in(&self.identifier, catalog)
+            .or_else(|| self.aliases.iter().find_map(|alias| alias.in_catalog(catalog)))
+    }
+
+    // Case- and whitespace-insensitive match against the identifier, proper name and aliases.
+    pub fn matches_name(&self, query: &str) -> bool {
+        identifier::names_match(&self.identifier, query)
+            || (!self.name.is_empty() && identifier::names_match(&self.name, query))
+            || self.aliases.iter().any(|alias| alias.matches(query))
+    }
+
+    // The proper name if there is one, else a Bayer or Flamsteed designation from the identifier or
+    // the aliases, else the identifier without SIMBAD's "*"/"V*" prefix, with Greek letters in `style`.
+    pub fn display_name(&self, style: DisplayNameStyle) -> String {
+        if !self.name.trim().is_empty() {
+            return designation::convert_greek(&self.name, style);
+        }
+        std::iter::once(self.identifier.as_str()).chain(self.aliases.iter().map(Identifier::as_str))
+            .find_map(designation::parse_designation)
+            .map(|d| d.format(style))
+            .unwrap_or_else(|| {
+                let identifier = self.identifier.trim();
+                let identifier = identifier.strip_prefix("V*").or_else(|| identifier.strip_prefix('*')).unwrap_or(identifier);
+                designation::convert_greek(identifier, style)
+            })
+    }
+
+    pub fn has_spectral_type(&self) -> bool {
+        !self.class.is_empty() && self.class != UNKNOWN_SPECTRAL_TYPE
+    }
+
+    // None for `UNKNOWN_SPECTRAL_TYPE` as well as for classes that don't parse.
+    pub fn spectral_type(&self) -> Option<SpectralType> {
+        self.has_spectral_type().then(|| self.class.parse().ok()).flatten()
+    }
+
+    pub fn distance(&self) -> f32 {
+        self.pos.length()
+    }
+
+    pub fn light_travel_time(&self) -> Option<f32> {
+        let distance = self.distance();
+        (distance > 0.).then(|| Distance::from_light_years(distance).light_travel_time())
+    }
+
+    pub fn lookback_time(&self, cosmology: &Cosmology) -> Option<f64> {
+        self.redshift.map(|z| cosmology.lookback_time(z as f64))
+    }
+
+    pub fn normalize_to_epoch(&mut self, jd: f64) {
+        if let Some(pm) = self.proper_motion {
+            let position = StellarPosition::from(self.pos);
+            let coord = pm.apply(&position.coord, (jd - self.epoch) / 365.25);
+            self.pos = StellarPosition::new(position.distance, coord.right_ascension, coord.declination).into();
+        }
+        self.epoch = jd;
+    }
+}
+
+// Magnitudes by `Band`; read and write them through `get`, `set`, `with` and `iter`.
+#[cfg_attr(feature = "schemars", derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema))]
+#[derive(Default, Copy, Clone, Debug)]
+pub struct Photometry {
+    magnitudes: [Option<f32>; Band::ALL.len()]
+}
+
+impl Photometry {
+    // Combined brightness of two unresolved sources, band by band.
+    pub fn combine(&self, other: &Photometry) -> Photometry {
+        let mut combined = Photometry::default();
+        for band in Band::ALL {
+            combined.set(band, match (self.get(band), other.get(band)) {
+                (Some(a), Some(b)) => Some(-2.5 * (10f32.powf(-0.4 * a) + 10f32.powf(-0.4 * b)).log10()),
+                (a, b) => a.or(b)
+            });
+        }
+        combined
+    }
+}
+
+impl From<&Record> for Photometry {
+    fn from(record: &Record) -> Self {
+        Self::default()
+            .with(Band::U, record.mag_u)
+            .with(Band::B, record.mag_b)
+            .with(Band::V, record.mag_v)
+            .with(Band::R, record.mag_r)
+            .with(Band::I, record.mag_i)
+            .with(Band::G, record.mag_g)
+            .with(Band::J, record.mag_j)
+            .with(Band::H, record.mag_h)
+            .with(Band::K, record.mag_k)
+    }
 }
 
 #[derive(Default, Copy, Clone, Debug)]
@@ -20,12 +275,51 @@ pub struct StellarPosition {
 }
 
 impl StellarPosition {
+    // Distance in light-years, angles in radians. Prefer the unit-named constructors below.
     pub fn new(distance: f32, right_ascension: f32, declination: f32) -> Self {
         Self {
             distance,
             coord : EquatorialCoordinate::new(right_ascension, declination)
         }
     }
+
+    pub fn from_parsecs_and_radians(parsecs: f32, right_ascension: f32, declination: f32) -> Self {
+        Self::new(Distance::from_parsecs(parsecs).light_years(), right_ascension, declination)
+    }
+
+    pub fn from_parsecs_and_degrees(parsecs: f32, right_ascension: f32, declination: f32) -> Self {
+        Self::from_parsecs_and_radians(parsecs, right_ascension.to_radians(), declination.to_radians())
+    }
+
+    pub fn from_lightyears_and_radians(light_years: f32, right_ascension: f32, declination: f32) -> Self {
+        Self::new(light_years, right_ascension, declination)
+    }
+
+    pub fn from_lightyears_hms_dms(light_years: f32, right_ascension: HourAngle, declination: Degree) -> Self {
+        Self::new(light_years, right_ascension.to_radians(), declination.to_f32().to_radians())
+    }
+
+    // Apparent place as seen by `observer` at Julian date `jd`: annual (and diurnal) parallax,
+    // plus annual and diurnal aberration if requested. A distance of 0 is treated as unknown.
+    pub fn apparent_at(&self, jd: f64, observer: &Observer, aberration: bool) -> Self {
+        let ra = self.coord.right_ascension as f64;
+        let dec = self.coord.declination as f64;
+        let direction = DVec3::new(dec.cos() * ra.cos(), dec.cos() * ra.sin(), dec.sin());
+        let (mut apparent, distance) = if self.distance > 0. {
+            let origin = ephemeris::earth_position(jd) + observer.geocentric_position(jd);
+            let pos = direction * (self.distance as f64 * ephemeris::AU_PER_LIGHT_YEAR) - origin;
+            (pos.normalize(), pos.length() / ephemeris::AU_PER_LIGHT_YEAR)
+        } else {
+            (direction, 0.)
+        };
+        if aberration {
+            let velocity = ephemeris::earth_velocity(jd) + observer.geocentric_velocity(jd);
+            apparent = (apparent + velocity / ephemeris::SPEED_OF_LIGHT).normalize();
+        }
+        let ra = apparent.y.atan2(apparent.x).wrap_positive();
+        let dec = apparent.z.clamp(-1., 1.).asin();
+        Self::new(distance as f32, ra as f32, dec as f32)
+    }
 }
 
 impl Display for StellarPosition {
@@ -49,10 +343,7 @@ impl From<Vec3> for StellarPosition {
         let plane_vec = Vec2::new(value.x, value.y);
         let adj = plane_vec.length();
         let dec = if hyp != 0. {(adj/hyp).acos() * value.z.signum()} else { 0. };
-        let ra = (plane_vec.y.abs()).atan2(plane_vec.x.abs());
-        let x = plane_vec.x;
-        let y = plane_vec.y;
-        let ra = if x >= 0. && y >= 0. { ra } else if x >= 0. && y <= 0. { 180f32.to_radians() -ra } else if x <= 0. && y <= 0. { 180f32.to_radians() + ra} else { 360f32.to_radians() - ra };
+        let ra = plane_vec.y.atan2(plane_vec.x).wrap_positive();
         Self {
             distance: hyp,
             coord: EquatorialCoordinate {
@@ -72,7 +363,7 @@ pub struct EquatorialCoordinate {
 
 impl EquatorialCoordinate {
     pub fn new(right_ascension: f32, declination: f32) -> Self {
-        let right_ascension = right_ascension % (std::f32::consts::PI*2.);
+        let right_ascension = right_ascension.wrap_positive();
         let declination = declination.max(-90f32.to_radians()).min(90f32.to_radians()); // TODO: Is there a cleaner way to do this?
         Self {
             right_ascension,
@@ -83,13 +374,107 @@ impl EquatorialCoordinate {
     pub fn from_hour_angle(hour_angle: HourAngle, declination: f32) -> Self {
         Self::new(hour_angle.to_radians(), declination)
     }
+
+    // Great-circle distance in radians (haversine, accurate for small separations).
+    pub fn separation(&self, other: &EquatorialCoordinate) -> f32 {
+        let d_dec = other.declination - self.declination;
+        let d_ra = other.right_ascension - self.right_ascension;
+        let a = (d_dec / 2.).sin().powi(2) + self.declination.cos() * other.declination.cos() * (d_ra / 2.).sin().powi(2);
+        2. * a.sqrt().min(1.).asin()
+    }
+
+    pub fn approx_eq(&self, other: &EquatorialCoordinate, tol_arcsec: f32) -> bool {
+        self.separation(other) <= (tol_arcsec / 3600.).to_radians()
+    }
+
+    // Cell of a roughly equal-area grid with cells at least `arcsec_resolution` wide: declination
+    // bands, each split into as many RA cells as fit along its centre line.
+    pub fn cell_key(&self, arcsec_resolution: f32) -> CellKey {
+        let (band, cells) = cell_band(self.declination, arcsec_resolution);
+        let cell = ((self.right_ascension as f64).wrap_positive() / std::f64::consts::TAU * cells as f64) as i32;
+        CellKey { band, cell: cell.min(cells - 1) }
+    }
+
+    // Keys of every cell containing a point within `arcsec_resolution` of this one. Two
+    // coordinates closer than the resolution can sit on either side of a cell border, so
+    // duplicate detection should look these up rather than just `cell_key`. The RA span is the
+    // exact width of the search cap, and a cap reaching over a pole takes whole bands, so this
+    // stays complete at any declination.
+    pub fn cell_keys_near(&self, arcsec_resolution: f32) -> Vec<CellKey> {
+        use std::f64::consts::{FRAC_PI_2, TAU};
+        let radius = (arcsec_resolution as f64 / 3600.).to_radians();
+        let declination = self.declination as f64;
+        let first = cell_band_index(declination - radius, arcsec_resolution);
+        let last = cell_band_index(declination + radius, arcsec_resolution);
+        let covers_pole = declination.abs() + radius >= FRAC_PI_2;
+        // Half-width in RA of the cap, widest where it touches its tangent meridians.
+        let ra_offset = if covers_pole { TAU } else { (radius.sin() / declination.cos()).min(1.).asin() };
+        let right_ascension = (self.right_ascension as f64).wrap_positive();
+        let mut keys = vec![];
+        for band in first..=last {
+            let cells = cells_in_band(band, arcsec_resolution);
+            let width = TAU / cells as f64;
+            let from = ((right_ascension - ra_offset) / width).floor() as i64;
+            let to = ((right_ascension + ra_offset) / width).floor() as i64;
+            if to - from + 1 >= cells as i64 {
+                keys.extend((0..cells).map(|cell| CellKey { band, cell }));
+                continue;
+            }
+            keys.extend((from..=to).map(|cell| CellKey { band, cell: cell.rem_euclid(cells as i64) as i32 }));
+        }
+        keys.sort();
+        keys.dedup();
+        keys
+    }
+
+    // Direction on the unit sphere, x towards RA 0h and z towards the north pole; the same frame
+    // as `Star::pos`. Comparing these avoids the RA singularity at the poles.
+    pub fn unit_vector(&self) -> Vec3 {
+        let (sin_ra, cos_ra) = self.right_ascension.sin_cos();
+        let (sin_dec, cos_dec) = self.declination.sin_cos();
+        Vec3::new(cos_dec * cos_ra, cos_dec * sin_ra, sin_dec)
+    }
+}
+
+fn cell_bands(arcsec_resolution: f32) -> f64 {
+    let resolution = (arcsec_resolution as f64 / 3600.).to_radians();
+    (std::f64::consts::PI / resolution).floor().max(1.)
+}
+
+fn cell_band_index(declination: f64, arcsec_resolution: f32) -> i32 {
+    use std::f64::consts::{FRAC_PI_2, PI};
+    let bands = cell_bands(arcsec_resolution);
+    let declination = declination.clamp(-FRAC_PI_2, FRAC_PI_2);
+    (((declination + FRAC_PI_2) / PI * bands) as i64).clamp(0, bands as i64 - 1) as i32
+}
+
+// Number of RA cells in a declination band.
+fn cells_in_band(band: i32, arcsec_resolution: f32) -> i32 {
+    use std::f64::consts::{FRAC_PI_2, PI, TAU};
+    let resolution = (arcsec_resolution as f64 / 3600.).to_radians();
+    let centre = -FRAC_PI_2 + (band as f64 + 0.5) * PI / cell_bands(arcsec_resolution);
+    (TAU * centre.cos() / resolution).floor().max(1.) as i32
+}
+
+// Declination band index and number of RA cells in it.
+fn cell_band(declination: f32, arcsec_resolution: f32) -> (i32, i32) {
+    let band = cell_band_index(declination as f64, arcsec_resolution);
+    (band, cells_in_band(band, arcsec_resolution))
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CellKey {
+    pub band: i32,
+    pub cell: i32
 }
 
 #[derive(Default, Copy, Clone, Debug)]
 pub struct Degree {
     pub base: i16,
     pub arc_mins: u8,
-    pub arc_secs: f32
+    pub arc_secs: f32,
+    // Separate from `base` so that e.g. -00°17′ keeps its sign.
+    pub negative: bool
 }
 
 
@@ -99,11 +484,29 @@ impl Degree {
             base,
             arc_mins,
             arc_secs,
+            negative: base < 0,
         }
     }
 
     pub fn to_f32(self) -> f32 {
-        self.base as f32 + (self.arc_mins as f32)/60. + (self.arc_secs)/3600.
+        let value = self.base.unsigned_abs() as f32 + (self.arc_mins as f32)/60. + (self.arc_secs)/3600.;
+        if self.negative { -value } else { value }
+    }
+}
+
+impl FromStr for Degree {
+    type Err = SimbadError;
+
+    // Accepts "-16°42′58″", "-16:42:58", "-16 42 58" and "-16d42m58s"; seconds are optional.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, base, arc_mins, arc_secs) = parse_sexagesimal_parts(s, "degrees")?;
+        let base = i16::try_from(base).map_err(|_| SimbadError::InvalidAngle(format!("degrees out of range in \"{}\"", s.trim())))?;
+        Ok(Self {
+            base: if negative { -base } else { base },
+            arc_mins,
+            arc_secs,
+            negative,
+        })
     }
 }
 
@@ -139,48 +542,209 @@ impl HourAngle {
     }
 }
 
+impl FromStr for HourAngle {
+    type Err = SimbadError;
+
+    // Accepts "06h45m08.9s", "06:45:08.9" and "06 45 08.9"; seconds are optional.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, hours, minutes, seconds) = parse_sexagesimal_parts(s, "hour angle")?;
+        if negative || hours >= 24 {
+            return Err(SimbadError::InvalidAngle(format!("hours must be within 0..24 in \"{}\"", s.trim())));
+        }
+        Ok(Self::new(hours as u8, minutes, seconds))
+    }
+}
+
+fn parse_sexagesimal_parts(s: &str, what: &str) -> Result<(bool, u32, u8, f32), SimbadError> {
+    let s = s.trim().replace('−', "-");
+    let (negative, body) = match s.strip_prefix('-') {
+        Some(body) => (true, body),
+        None => (false, s.strip_prefix('+').unwrap_or(&s))
+    };
+    let normalized = body.chars().map(|c| if "hdms:°′″'\"".contains(c) { ' ' } else { c }).collect::<String>();
+    let tokens = normalized.split_whitespace().collect::<Vec<_>>();
+    if !(2..=3).contains(&tokens.len()) {
+        return Err(SimbadError::InvalidAngle(format!("expected 2 or 3 components in {} \"{}\", found {}", what, s, tokens.len())));
+    }
+    let whole = tokens[0].parse::<u32>().map_err(|_| SimbadError::InvalidAngle(format!("invalid whole {} \"{}\" in \"{}\"", what, tokens[0], s)))?;
+    let minutes = tokens[1].parse::<u8>().ok().filter(|m| *m < 60).ok_or_else(|| SimbadError::InvalidAngle(format!("invalid minutes \"{}\" in \"{}\"", tokens[1], s)))?;
+    let seconds = match tokens.get(2) {
+        Some(token) => token.parse::<f32>().ok().filter(|sec| (0. ..60.).contains(sec)).ok_or_else(|| SimbadError::InvalidAngle(format!("invalid seconds \"{}\" in \"{}\"", token, s)))?,
+        None => 0.
+    };
+    Ok((negative, whole, minutes, seconds))
+}
+
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Record {
+    #[serde(skip)]
+    line: u64,
+    // Columns no `RecordSchema` knows, as (header, value).
+    #[serde(skip)]
+    extra: Vec<(String, String)>,
     #[serde(alias = "#")]
     id: usize,
     identifier: String,
     typ: String,
-    #[serde(alias = "coord1 (ICRS,J2000/2000)")]
+    #[serde(alias = "all ids", default, deserialize_with = "de::null_marker")]
+    ids: Option<String>,
+    #[serde(alias = "coord1 (ICRS,J2000/2000)", default, deserialize_with = "de::null_marker")]
     coord1: Option<String>,
-    #[serde(alias = "coord2 (FK5,J2000/2000)")]
+    #[serde(alias = "coord2 (FK5,J2000/2000)", default, deserialize_with = "de::null_marker")]
     coord2: Option<String>,
-    #[serde(alias = "coord3 (FK4,B1950/1950)")]
+    #[serde(alias = "coord3 (FK4,B1950/1950)", default, deserialize_with = "de::null_marker")]
     coord3: Option<String>,
-    #[serde(alias = "coord4 (Gal,J2000/2000)")]
+    #[serde(alias = "coord4 (Gal,J2000/2000)", default, deserialize_with = "de::null_marker")]
     coord4: Option<String>,
+    // Error ellipse of the coordinates, named as in SIMBAD's TAP `basic` table.
+    #[serde(default, deserialize_with = "de::null_marker")]
+    coo_err_maj: Option<f32>,
+    #[serde(default, deserialize_with = "de::null_marker")]
+    coo_err_min: Option<f32>,
+    #[serde(default, deserialize_with = "de::null_marker")]
+    coo_err_angle: Option<f32>,
+    #[serde(default, deserialize_with = "de::null_marker")]
     pm : Option<String>,
+    #[serde(default, deserialize_with = "de::null_marker")]
     plx: Option<f32>,
+    #[serde(default, deserialize_with = "de::null_marker")]
     radvel: Option<f32>,
+    #[serde(default, deserialize_with = "de::null_marker")]
     redshift: Option<f32>,
+    #[serde(default, deserialize_with = "de::null_marker")]
     cz: Option<f32>,
-    #[serde(alias = "Mag U")]
+    #[serde(alias = "Mag U", default, deserialize_with = "de::null_marker")]
     mag_u: Option<f32>,
-    #[serde(alias = "Mag B")]
+    #[serde(alias = "Mag B", default, deserialize_with = "de::null_marker")]
     mag_b: Option<f32>,
-    #[serde(alias = "Mag V")]
+    #[serde(alias = "Mag V", default, deserialize_with = "de::null_marker")]
     mag_v: Option<f32>,
-    #[serde(alias = "Mag R")]
+    #[serde(alias = "Mag R", default, deserialize_with = "de::null_marker")]
     mag_r: Option<f32>,
-    #[serde(alias = "Mag I")]
+    #[serde(alias = "Mag I", default, deserialize_with = "de::null_marker")]
     mag_i: Option<f32>,
-    #[serde(alias = "spec. type")]
+    #[serde(alias = "Mag G", default, deserialize_with = "de::null_marker")]
+    mag_g: Option<f32>,
+    #[serde(alias = "Mag J", default, deserialize_with = "de::null_marker")]
+    mag_j: Option<f32>,
+    #[serde(alias = "Mag H", default, deserialize_with = "de::null_marker")]
+    mag_h: Option<f32>,
+    #[serde(alias = "Mag K", default, deserialize_with = "de::null_marker")]
+    mag_k: Option<f32>,
+    #[serde(alias = "spec. type", default, deserialize_with = "de::null_marker")]
     spec_type: Option<String>,
-    #[serde(alias = "morph. type")]
+    #[serde(alias = "morph. type", default, deserialize_with = "de::null_marker")]
     morph_type: Option<String>,
-    #[serde(alias = "ang. size")]
+    #[serde(alias = "ang. size", default, deserialize_with = "de::null_marker")]
     ang_size: Option<String>,
-    #[serde(alias = "pretty name")]
-    pretty_name: Option<String>
+    #[serde(alias = "pretty name", default, deserialize_with = "de::null_marker")]
+    pretty_name: Option<String>,
+    #[serde(alias = "var. type", default, deserialize_with = "de::null_marker")]
+    var_type: Option<String>,
+    #[serde(alias = "var. period", default, deserialize_with = "de::null_marker")]
+    var_period: Option<f32>,
+    #[serde(alias = "var. amplitude", default, deserialize_with = "de::null_marker")]
+    var_amplitude: Option<f32>
+}
+
+impl Record {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    // Line of the row in the source file.
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
+    pub fn extra(&self) -> &[(String, String)] {
+        &self.extra
+    }
+
+    pub fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    pub fn set_identifier(&mut self, identifier: String) {
+        self.identifier = identifier;
+    }
+
+    // SIMBAD object type, e.g. "*", "SB*" or "PM*".
+    pub fn object_type(&self) -> &str {
+        &self.typ
+    }
+
+    pub fn spectral_type(&self) -> Option<&str> {
+        self.spec_type.as_deref()
+    }
+}
+
+// For re-exporting a filtered catalog in SIMBAD's layout. The coordinates go into the ICRS and
+// galactic columns, the parallax is derived from the distance; the object type, the FK5/FK4
+// coordinates, cz, morphology and angular size aren't kept on a `Star` and stay empty.
+impl From<&Star> for Record {
+    fn from(star: &Star) -> Self {
+        let pos = StellarPosition::from(star.pos);
+        let galactic = GalacticCoordinate::from(pos.coord);
+        let mut ids = vec![star.identifier.clone()];
+        ids.extend(star.aliases.iter().map(ToString::to_string));
+        let p = &star.photometry;
+        Self {
+            line: 0,
+            extra: vec![],
+            id: star.id,
+            identifier: star.identifier.clone(),
+            typ: String::new(),
+            ids: (!star.aliases.is_empty()).then(|| ids.join("|")),
+            coord1: Some(format_sexagesimal(&pos.coord)),
+            coord2: None,
+            coord3: None,
+            coord4: Some(format!("{:.4} {:+.4}", galactic.longitude.to_degrees(), galactic.latitude.to_degrees())),
+            coo_err_maj: star.coord_uncertainty.map(|e| e.maj_mas),
+            coo_err_min: star.coord_uncertainty.map(|e| e.min_mas),
+            coo_err_angle: star.coord_uncertainty.map(|e| e.pa_deg),
+            pm: star.proper_motion.map(|pm| pm.to_string()),
+            // mas; `pos.distance` is in light-years.
+            plx: (pos.distance > 0.).then(|| 1000. / Distance::from_light_years(pos.distance).parsecs()),
+            radvel: star.radial_velocity,
+            redshift: star.redshift,
+            cz: None,
+            mag_u: p.get(Band::U),
+            mag_b: p.get(Band::B),
+            mag_v: p.get(Band::V),
+            mag_r: p.get(Band::R),
+            mag_i: p.get(Band::I),
+            mag_g: p.get(Band::G),
+            mag_j: p.get(Band::J),
+            mag_h: p.get(Band::H),
+            mag_k: p.get(Band::K),
+            spec_type: star.has_spectral_type().then(|| star.class.clone()),
+            morph_type: None,
+            ang_size: None,
+            pretty_name: (!star.name.is_empty()).then(|| star.name.clone()),
+            var_type: star.variability.as_ref().map(|v| v.var_type.clone()),
+            var_period: star.variability.as_ref().and_then(|v| v.period_days),
+            var_amplitude: star.variability.as_ref().and_then(|v| v.amplitude),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub enum SimbadError {
     CoordNotFound,
+    InvalidCoordinate,
+    InvalidAngle(String),
+    InvalidSpectralType,
+    InvalidConstellation,
+    InvalidProperMotion,
+    InvalidFormat,
+    UnsupportedVersion(u16),
+    RowOutOfRange(usize),
     Unspecified
 }
 
@@ -192,67 +756,406 @@ impl Display for SimbadError {
 
 impl std::error::Error for SimbadError {}
 
+#[derive(Debug)]
+pub struct ImportReport<T = Record> {
+    pub records: Vec<T>,
+    pub skipped: Vec<(u64, csv::Error)>,
+    pub warnings: Vec<ImportWarning>
+}
+
+impl<T> Default for ImportReport<T> {
+    fn default() -> Self {
+        Self {
+            records: vec![],
+            skipped: vec![],
+            warnings: vec![],
+        }
+    }
+}
+
 pub fn import_records<P: AsRef<Path>>(path: P) -> Result<Vec<Record>, Box<dyn std::error::Error>> {
-    let mut rdr = ReaderBuilder::new().delimiter(';' as u8).from_path(path)?;
-    let mut records = vec![];
-    for result in rdr.deserialize::<Record>() {
-        if let Ok(record) = result {
-            records.push(record);
+    Ok(import_records_with_report(path)?.records)
+}
+
+pub fn import_records_with_report<P: AsRef<Path>>(path: P) -> Result<ImportReport, Box<dyn std::error::Error>> {
+    read_records(path, &ImportOptions::default(), ColumnSet::All)
+}
+
+// Honours the row range and number format of `options`; `columns` is separate since object import
+// always needs every column.
+pub(crate) fn read_records<P: AsRef<Path>>(path: P, options: &ImportOptions, columns: ColumnSet) -> Result<ImportReport, Box<dyn std::error::Error>> {
+    read_records_from(std::io::BufReader::new(std::fs::File::open(path)?), options, columns)
+}
+
+// Reads no further than the CSV reader's buffer past the last row `limit` lets through.
+pub(crate) fn read_records_from<R: std::io::BufRead>(input: R, options: &ImportOptions, columns: ColumnSet) -> Result<ImportReport, Box<dyn std::error::Error>> {
+    let cleaned = preamble::CleanReader::new(input)?;
+    let mut rdr = ReaderBuilder::new().delimiter(cleaned.delimiter as u8).from_reader(cleaned);
+    let mut report = ImportReport::default();
+    let header = ResolvedHeader::new(rdr.headers()?);
+    if let Some(unit) = header.parallax_unit().filter(|_| options.parallax_unit == ParallaxUnit::Auto) {
+        report.warnings.push(ImportWarning::ParallaxUnitDetected(unit));
+    }
+    if columns == ColumnSet::All && !header.unknown_columns().is_empty() {
+        report.warnings.push(ImportWarning::UnknownColumns(header.unknown_columns()));
+    }
+    // Skipped rows are only split into fields, into one reused buffer.
+    let mut skipped = csv::ByteRecord::new();
+    for _ in 0..options.skip {
+        if let Ok(false) = rdr.read_byte_record(&mut skipped) {
+            break;
         }
     }
-    Ok(records)
+    let mut rows = rdr.records();
+    for _ in 0..options.limit.unwrap_or(usize::MAX) {
+        let Some(result) = rows.next() else { break };
+        // Taken once the row is read: the cleaned stream only knows which lines it dropped up to
+        // where the CSV reader got.
+        let position = match &result {
+            Ok(row) => row.position(),
+            Err(err) => err.position()
+        };
+        let line = position.map_or(0, |pos| rows.reader().get_ref().original_line(pos.line()));
+        let result = match options.numbers {
+            NumberFormat::Strict => result,
+            NumberFormat::Tolerant => result.map(|row| de::tolerant_row(&row, &header))
+        };
+        match result.and_then(|row| header.record_with(&row, columns)) {
+            Ok(mut record) => {
+                record.line = line;
+                report.records.push(record);
+            }
+            Err(err) => {
+                let line = err.position().map_or(line, |pos| rows.reader().get_ref().original_line(pos.line()));
+                report.skipped.push((line, err));
+            }
+        }
+    }
+    let cleaned = rdr.get_ref();
+    if cleaned.preamble > 0 || cleaned.trailing > 0 {
+        report.warnings.insert(0, ImportWarning::IgnoredLines { preamble: cleaned.preamble, trailing: cleaned.trailing });
+    }
+    Ok(report)
+}
+
+// Writes records in the ';' separated layout of a SIMBAD ASCII export, so they read back with
+// `import_records` and `import`.
+pub fn write_records<W: std::io::Write>(records: &[Record], w: W) -> Result<(), Box<dyn std::error::Error>> {
+    let mut wtr = WriterBuilder::new().delimiter(b';').has_headers(false).from_writer(w);
+    wtr.write_record(schema::FIELDS.iter().map(|field| RecordSchema::ASCII.column(field).unwrap_or(field)))?;
+    for record in records {
+        wtr.serialize(record)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn export_records<P: AsRef<Path>>(records: &[Record], path: P) -> Result<(), Box<dyn std::error::Error>> {
+    write_records(records, std::io::BufWriter::new(std::fs::File::create(path)?))
 }
 
 pub fn import<P: AsRef<Path>>(path: P) -> Result<Vec<Star>, Box<dyn std::error::Error>> {
-    let records = import_records(path)?;
-    let mut stars = vec![];
+    Ok(import_with_options(path, &ImportOptions::default())?.records)
+}
+
+pub fn import_with_options<P: AsRef<Path>>(path: P, options: &ImportOptions) -> Result<ImportReport<Star>, Box<dyn std::error::Error>> {
+    let source = Source::File(path.as_ref().to_path_buf());
+    let ImportReport { records, skipped, mut warnings } = read_records(path, options, options.columns)?;
+    let records = records.into_iter().filter_map(|record| options.apply_record(record)).collect::<Vec<_>>();
+    let parallax_unit = resolve_parallax_unit(options, &warnings);
+    let stars = stars_from_records(records, &source, parallax_unit, options, &mut warnings)?;
+    Ok(ImportReport { records: stars, skipped, warnings })
+}
+
+// `read_records` already reported a unit named in the header as `ParallaxUnitDetected`.
+pub(crate) fn resolve_parallax_unit(options: &ImportOptions, warnings: &[ImportWarning]) -> ParallaxUnit {
+    match options.parallax_unit {
+        ParallaxUnit::Auto => warnings.iter()
+            .find_map(|w| match w {
+                ImportWarning::ParallaxUnitDetected(unit) => Some(*unit),
+                _ => None
+            })
+            .unwrap_or(ParallaxUnit::Milliarcseconds),
+        unit => unit
+    }
+}
+
+// Records already went through `ImportOptions::map_record`.
+pub(crate) fn stars_from_records(records: Vec<Record>, source: &Source, parallax_unit: ParallaxUnit, options: &ImportOptions, warnings: &mut Vec<ImportWarning>) -> Result<Vec<Star>, Box<dyn std::error::Error>> {
+    let mut stars: Vec<Star> = vec![];
+    let mut systems: HashMap<StarKey, (usize, bool)> = HashMap::new();
+    let mut seen: HashMap<StarKey, usize> = HashMap::new();
     for record in records {
             if record.plx.is_none() { continue; }
-            let dist = 1./(record.plx.ok_or(SimbadError::Unspecified)?/1000.)*3.26;
-            let dist = if dist.is_finite() { dist } else { 0. };
-            let coord1 = parse_coord(record.coord1.as_ref().ok_or(SimbadError::CoordNotFound)?);
-            let coord2 = parse_coord(record.coord2.as_ref().ok_or(SimbadError::CoordNotFound)?);
-            let coord3 = parse_coord(record.coord3.as_ref().ok_or(SimbadError::CoordNotFound)?);
-            let coords = [coord1, coord2, coord3].into_iter().filter_map(|x| x).collect::<Vec<_>>();
-            let coord = average_coord(&coords);
-            let name = record.identifier;
-            if record.id == 0 { println!("{:#?}", dist)}
+            let plx = parallax_unit.to_arcseconds(record.plx.ok_or(SimbadError::Unspecified)?);
+            if !ParallaxUnit::is_plausible(plx) {
+                warnings.push(ImportWarning::ImplausibleParallax { id: record.id, parallax: plx });
+            }
+            // No distance, and 1/plx would put a negative parallax at the antipode.
+            if plx.is_nan() || plx <= 0. { continue; }
+            let dist = Distance::from_parsecs(1. / plx).light_years();
+            let Ok(coord) = record_coordinate(&record, warnings) else {
+                warnings.push(ImportWarning::MissingCoordinates { id: record.id, line: record.line });
+                continue;
+            };
+            let photometry = Photometry::from(&record);
+            let variability = Variability::from_record(&record);
+            let coord_uncertainty = CoordinateUncertainty::from_record(&record);
+            let (name, aliases) = record_identifiers(&record);
             let pos = StellarPosition::new(dist, coord.right_ascension, coord.declination);
-            if record.spec_type.is_none() { continue; }
-            let spec_type = record.spec_type.unwrap();
-            if name.ends_with("B") {continue;}
-            let name = record.pretty_name.unwrap_or_default();
+            if record.spec_type.is_none() && options.require_spectral_type { continue; }
+            let spec_type = record.spec_type.unwrap_or_else(|| UNKNOWN_SPECTRAL_TYPE.to_string());
+            let (system, component) = identifier::split_component(&name);
+            let primary = component.is_none_or(identifier::is_primary_component);
+            if options.component_policy == ComponentPolicy::PrimaryOnly && !primary { continue; }
+            let constellation = designation::parse_designation(system).map(|d| d.constellation());
+            let identifier = name.clone();
+            let name = record.pretty_name.map(|n| identifier::normalize_whitespace(&n)).unwrap_or_default();
             let star = Star {
                 id : record.id,
                 pos : pos.into(),
+                coord_uncertainty,
+                identifier,
+                aliases,
                 name,
                 class: spec_type,
-                constellation: "?".to_string(),
+                constellation,
+                photometry,
+                proper_motion: record.pm.as_deref().and_then(|pm| pm.parse().ok()),
+                redshift: record.redshift,
+                radial_velocity: record.radvel,
+                variability,
+                epoch: J2000,
+                provenance: vec![Provenance::new(source.clone(), Some(record.line))],
             };
+            let Some(star) = options.apply_star(star) else { continue };
+            let Some(key) = star.key() else {
+                stars.push(star);
+                continue;
+            };
+            if let Some(&index) = seen.get(&key) {
+                warnings.push(ImportWarning::DuplicateStar { id: star.id, key });
+                stars[index].provenance.extend(star.provenance);
+                continue;
+            }
+            if options.component_policy == ComponentPolicy::MergeSystems && !system.is_empty() {
+                let system = StarKey::from_identifier(system);
+                if let Some(&(index, merged_primary)) = systems.get(&system) {
+                    let merged = &mut stars[index];
+                    let photometry = merged.photometry.combine(&star.photometry);
+                    let mut provenance = std::mem::take(&mut merged.provenance);
+                    provenance.extend(star.provenance.iter().cloned());
+                    if primary && !merged_primary {
+                        *merged = star;
+                        systems.insert(system, (index, true));
+                    }
+                    merged.photometry = photometry;
+                    merged.provenance = provenance;
+                    seen.insert(key, index);
+                    continue;
+                }
+                systems.insert(system, (stars.len(), primary));
+            }
+            seen.insert(key, stars.len());
             stars.push(star);
     }
     Ok(stars)
 }
-fn parse_coord(input: &str) -> Option<EquatorialCoordinate> {
-    let splits = input.split_whitespace().collect::<Vec<_>>();
-    if splits.len() < 6 { return None; }
-    let ra = HourAngle::new(splits[0].parse::<u8>().ok()?, splits[1].parse::<u8>().ok()?, splits[2].parse::<f32>().ok()?);
-    let ra = ra.to_radians();
-    let dec = Degree::new((&splits[3][1..]).parse::<i16>().ok()?, splits[4].parse::<u8>().ok()?, splits[5].parse::<f32>().ok()?);
-    let dec = dec.to_f32();
-    let dec = (dec * if &splits[3][0..1] == "-" { -1. } else { 1. }).to_radians();
-    Some(EquatorialCoordinate::new(ra, dec))
+
+// The first column that parses, in the order ICRS (coord1), FK5 J2000 (coord2), FK4 B1950
+// (coord3, precessed to J2000) and galactic (coord4, with a `GalacticCoordinatesOnly` warning).
+pub(crate) fn record_coordinate(record: &Record, warnings: &mut Vec<ImportWarning>) -> Result<EquatorialCoordinate, SimbadError> {
+    // FK5 J2000 agrees with ICRS to a few tens of mas. Re-exported records (see
+    // `Record::from(&Star)`) leave the FK5 and FK4 columns empty.
+    let parse = |c: &Option<String>| c.as_deref().and_then(|c| parse::parse_coordinate_column(c).ok());
+    if let Some(coord) = parse(&record.coord1).or_else(|| parse(&record.coord2)) {
+        return Ok(coord);
+    }
+    if let Some(coord) = parse(&record.coord3) {
+        return Ok(frames::fk4_to_j2000(&coord));
+    }
+    // Some objects only have the galactic "l b" pair, in degrees.
+    let galactic = record.coord4.as_deref().and_then(|c| parse_decimal_degrees(c).ok()).ok_or(SimbadError::CoordNotFound)?;
+    warnings.push(ImportWarning::GalacticCoordinatesOnly { id: record.id });
+    Ok(EquatorialCoordinate::from(GalacticCoordinate::new(galactic.right_ascension, galactic.declination)))
 }
 
-fn parse_coord4(input: &str) -> Option<EquatorialCoordinate> {
-    let splits = input.split_whitespace().collect::<Vec<_>>();
-    let ra = splits[0].parse::<f32>().ok()?.to_radians();
-    let dec = splits[1].parse::<f32>().ok()?.to_radians();
-    Some(EquatorialCoordinate::new(ra, dec))
+// Main identifier (the first of the identifier column) and the remaining distinct aliases from
+// the identifier and "all ids" columns.
+pub(crate) fn record_identifiers(record: &Record) -> (String, Vec<Identifier>) {
+    let mut ids = identifier::split_aliases(&record.identifier);
+    ids.extend(record.ids.as_deref().map(identifier::split_aliases).unwrap_or_default());
+    let name = ids.first().map(ToString::to_string).unwrap_or_default();
+    let mut aliases: Vec<Identifier> = vec![];
+    for id in ids.into_iter().skip(1) {
+        if id.as_str() != name && !aliases.contains(&id) {
+            aliases.push(id);
+        }
+    }
+    (name, aliases)
 }
 
-fn average_coord(coords: &[EquatorialCoordinate]) -> EquatorialCoordinate {
-    let ra = coords.iter().map(|x| x.right_ascension).sum::<f32>()/(coords.len() as f32);
-    let dec = coords.iter().map(|x| x.declination).sum::<f32>()/(coords.len() as f32);
-    EquatorialCoordinate::new(ra, dec)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{assert_golden, RecordBuilder, SimbadCsv};
+
+    pub(crate) fn golden(name: &str) -> String {
+        format!("{}/tests/golden/{}", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    // One line per star with what import derived from its record.
+    pub(crate) fn describe(stars: &[Star]) -> String {
+        stars.iter().map(|star| {
+            let pos = StellarPosition::from(star.pos);
+            format!(
+                "{} {:?} name={:?} class={:?} ra={:.4} dec={:.4} ly={:.2} V={:?} con={:?} aliases={}\n",
+                star.id, star.identifier, star.name, star.class, pos.coord.right_ascension.to_degrees(),
+                pos.coord.declination.to_degrees(), pos.distance, star.photometry.get(Band::V),
+                star.constellation.map(|c| c.abbreviation()), star.aliases.len()
+            )
+        }).collect()
+    }
+
+    pub(crate) fn sample_records() -> Vec<Record> {
+        vec![
+            RecordBuilder::new(1, "* alf CMa").object_type("SB*").ids(&["* alf CMa", "HD  48915", "NAME Sirius"])
+                .coordinates("06 45 08.917 -16 42 58.02").fk4_coordinates("06 42 56.7 -16 38 46").galactic_coordinates("227.23 -08.89")
+                .coordinate_uncertainty(0.5, 0.4, 90.).parallax(379.21).proper_motion(-546.01, -1223.07).radial_velocity(-5.5)
+                .magnitude(Band::V, -1.46).magnitude(Band::B, -1.46).spectral_type("A1V").pretty_name("Sirius").build(),
+            RecordBuilder::new(2, "* alf Lyr").object_type("dS*").coordinates("18 36 56.336 +38 47 01.28")
+                .parallax(130.23).magnitude(Band::V, 0.03).spectral_type("A0Va").variability("dS*", Some(0.19), Some(0.04)).build(),
+            RecordBuilder::new(3, "* alf Cen A").object_type("SB*").coordinates("14 39 36.49 -60 50 02.3")
+                .parallax(742.12).magnitude(Band::V, 0.01).spectral_type("G2V").pretty_name("Rigil Kentaurus").build()
+        ]
+    }
+
+    #[test]
+    fn rendered_export_matches_golden() {
+        let csv = SimbadCsv::new(sample_records()).preamble(&["C.D.S.  -  SIMBAD4", "", "----"]).footer(true);
+        assert_golden(golden("export.csv"), &csv.render());
+    }
+
+    #[test]
+    fn imported_stars_match_golden() {
+        let path = SimbadCsv::new(sample_records()).write_temp("golden-import.csv").unwrap();
+        let report = import_with_options(&path, &ImportOptions::default()).unwrap();
+        assert!(report.skipped.is_empty());
+        assert_golden(golden("import.txt"), &describe(&report.records));
+    }
+
+    fn coordinate_of(record: &Record) -> (EquatorialCoordinate, Vec<ImportWarning>) {
+        let mut warnings = vec![];
+        (record_coordinate(record, &mut warnings).unwrap(), warnings)
+    }
+
+    fn arcsec_between(a: &EquatorialCoordinate, b: &str) -> f64 {
+        separations_batch(a, &[parse_sexagesimal(b).unwrap()])[0].to_degrees() * 3600.
+    }
+
+    #[test]
+    fn coordinate_prefers_icrs_then_fk5_without_averaging() {
+        let vega = "18 36 56.336 +38 47 01.28";
+        let mut record = RecordBuilder::new(1, "* alf Lyr").coordinates(vega).fk4_coordinates("18 35 14.7 +38 44 10").build();
+        record.coord2 = Some("18 36 56.400 +38 47 02.00".to_string());
+        let (coord, warnings) = coordinate_of(&record);
+        assert!(arcsec_between(&coord, vega) < 0.05);
+        assert!(warnings.is_empty());
+        record.coord1 = None;
+        assert!(arcsec_between(&coordinate_of(&record).0, "18 36 56.400 +38 47 02.00") < 0.05);
+    }
+
+    #[test]
+    fn coordinate_precesses_fk4_when_it_is_the_only_equatorial_column() {
+        let record = RecordBuilder::new(1, "* alf Lyr").fk4_coordinates("18 35 14.7 +38 44 10").build();
+        let (coord, warnings) = coordinate_of(&record);
+        // Vega's proper motion over the 50 years between B1950 and J2000 is about 17".
+        assert!(arcsec_between(&coord, "18 36 56.336 +38 47 01.28") < 20.);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn coordinate_falls_back_to_galactic() {
+        let record = RecordBuilder::new(7, "NAME Galactic Center").galactic_coordinates("0.00 0.00").build();
+        let (coord, warnings) = coordinate_of(&record);
+        assert!(arcsec_between(&coord, "17 45 37.2 -28 56 10") < 60.);
+        assert_eq!(warnings, [ImportWarning::GalacticCoordinatesOnly { id: 7 }]);
+        let mut warnings = vec![];
+        assert!(record_coordinate(&RecordBuilder::new(8, "nowhere").build(), &mut warnings).is_err());
+    }
+
+    // Counts the bytes handed out, to see how much of the input an import consumed.
+    struct Counting<'a> {
+        inner: &'a [u8],
+        read: std::rc::Rc<std::cell::Cell<usize>>
+    }
+
+    impl std::io::Read for Counting<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.read.set(self.read.get() + n);
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn limit_stops_reading_and_skip_counts_data_rows() {
+        let records = (1..=20_000).map(|id| RecordBuilder::new(id, &format!("HD {}", id)).coordinates("06 45 08.917 -16 42 58.02").build()).collect();
+        let text = SimbadCsv::new(records).preamble(&["query", "----"]).footer(true).render();
+        let read = std::rc::Rc::new(std::cell::Cell::new(0));
+        let input = std::io::BufReader::new(Counting { inner: text.as_bytes(), read: read.clone() });
+        let options = ImportOptions::new().skip(10).limit(5);
+        let report = read_records_from(input, &options, ColumnSet::All).unwrap();
+        assert_eq!(report.records.iter().map(|r| (r.id, r.line)).collect::<Vec<_>>(), (11..=15).map(|id| (id, id as u64 + 3)).collect::<Vec<_>>());
+        assert!(read.get() < text.len() / 10, "read {} of {} bytes", read.get(), text.len());
+        assert_eq!(report.warnings, [ImportWarning::IgnoredLines { preamble: 2, trailing: 0 }]);
+    }
+
+    #[test]
+    fn galaxies_and_untyped_stars_survive_import_as_unknown() {
+        let records = vec![
+            RecordBuilder::new(1, "NAME LMC").object_type("G").coordinates("05 23 34.6 -69 45 22").parallax(0.02)
+                .morph_type("SB(s)m").magnitude(Band::V, 0.4).build(),
+            RecordBuilder::new(2, "HD 1234").coordinates("00 16 40.2 -24 10 12").parallax(12.5).build(),
+            RecordBuilder::new(3, "* alf Lyr").coordinates("18 36 56.336 +38 47 01.28").parallax(130.23).spectral_type("A0Va").build()
+        ];
+        let path = SimbadCsv::new(records).write_temp("unknown-spectral-type.csv").unwrap();
+        let stars = import(&path).unwrap();
+        assert_eq!(stars.iter().map(|s| (s.id, s.class.as_str(), s.has_spectral_type())).collect::<Vec<_>>(), [
+            (1, UNKNOWN_SPECTRAL_TYPE, false), (2, UNKNOWN_SPECTRAL_TYPE, false), (3, "A0Va", true)
+        ]);
+        assert!(stars[0].spectral_type().is_none());
+        assert!(Record::from(&stars[1]).spec_type.is_none());
+        let strict = import_with_options(&path, &ImportOptions::new().require_spectral_type(true)).unwrap();
+        assert_eq!(strict.records.iter().map(|s| s.id).collect::<Vec<_>>(), [3]);
+    }
+
+    #[test]
+    fn display_names_keep_constellations_that_spell_greek_letters() {
+        let records = vec![
+            RecordBuilder::new(1, "V* CE Tau").coordinates("05 32 12.75 +18 35 39.2").parallax(1.8).build(),
+            RecordBuilder::new(2, "* pi. Tau").coordinates("04 26 36.4 +14 42 49.5").parallax(7.4).build()
+        ];
+        let path = SimbadCsv::new(records).write_temp("greek-constellations.csv").unwrap();
+        let names = import(&path).unwrap().iter().map(|s| s.display_name(DisplayNameStyle::Unicode)).collect::<Vec<_>>();
+        assert_eq!(names, ["CE Tau", "π Tau"]);
+    }
+
+    #[test]
+    fn rows_without_coordinates_are_skipped_not_fatal() {
+        let records = vec![
+            RecordBuilder::new(1, "HD 1234").coordinates("00 16 40.2 -24 10 12").parallax(12.5).build(),
+            RecordBuilder::new(2, "HD 5678").parallax(8.1).build(),
+            RecordBuilder::new(3, "NAME Nowhere Cloud").object_type("G").parallax(0.02).build(),
+            RecordBuilder::new(4, "* alf Lyr").coordinates("18 36 56.336 +38 47 01.28").parallax(130.23).build()
+        ];
+        let path = SimbadCsv::new(records).write_temp("missing-coordinates.csv").unwrap();
+        let report = import_with_options(&path, &ImportOptions::default()).unwrap();
+        assert_eq!(report.records.iter().map(|s| s.id).collect::<Vec<_>>(), [1, 4]);
+        let missing = [ImportWarning::MissingCoordinates { id: 2, line: 3 }, ImportWarning::MissingCoordinates { id: 3, line: 4 }];
+        assert_eq!(report.warnings, missing);
+        let objects = import_objects_with_options(&path, &ImportOptions::default()).unwrap();
+        assert_eq!(objects.records.len(), 2);
+        assert_eq!(objects.warnings, missing);
+    }
 }
@@ -0,0 +1,55 @@
+use std::collections::BTreeMap;
+use crate::{healpix, Band, Catalog, EquatorialCoordinate, Star, StellarPosition};
+
+// Tiles of about 3.7°, coarse enough that bright tiers don't end up with one star per tile.
+pub const LOD_NSIDE: u32 = 16;
+
+// Stars with V in [min_magnitude, max_magnitude), bucketed by NESTED HEALPix tile at `LOD_NSIDE`.
+#[derive(Clone, Debug)]
+pub struct LodTier<'a> {
+    pub min_magnitude: f32,
+    pub max_magnitude: f32,
+    pub tiles: BTreeMap<u64, Vec<&'a Star>>
+}
+
+impl<'a> LodTier<'a> {
+    pub fn len(&self) -> usize {
+        self.tiles.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    pub fn tile(&self, pixel: u64) -> &[&'a Star] {
+        self.tiles.get(&pixel).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn tile_at(&self, coord: &EquatorialCoordinate) -> &[&'a Star] {
+        self.tile(healpix::ang2pix_nest(LOD_NSIDE, coord))
+    }
+
+    pub fn stars(&self) -> impl Iterator<Item = &'a Star> + '_ {
+        self.tiles.values().flatten().copied()
+    }
+}
+
+impl Catalog {
+    // One tier per limit, e.g. [3., 6., 9.] gives V < 3, 3 <= V < 6 and 6 <= V < 9, so a renderer
+    // can load tiers progressively. Limits must be ascending; stars without V or fainter than the
+    // last limit are left out.
+    pub fn lod_tiers(&self, limits: &[f32]) -> Vec<LodTier<'_>> {
+        let mut tiers = limits.iter().scan(f32::NEG_INFINITY, |min, &max| {
+            let tier = LodTier { min_magnitude: *min, max_magnitude: max, tiles: BTreeMap::new() };
+            *min = max;
+            Some(tier)
+        }).collect::<Vec<_>>();
+        for star in &self.stars {
+            let Some(v) = star.photometry.get(Band::V) else { continue };
+            let Some(tier) = tiers.iter_mut().find(|t| v >= t.min_magnitude && v < t.max_magnitude) else { continue };
+            let pixel = healpix::ang2pix_nest(LOD_NSIDE, &StellarPosition::from(star.pos).coord);
+            tier.tiles.entry(pixel).or_default().push(star);
+        }
+        tiers
+    }
+}
@@ -0,0 +1,288 @@
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use crate::binary::FORMAT_VERSION;
+use crate::{Catalog, SimbadError};
+
+// How a dataset was produced: the query, the filters applied afterwards and the crate that wrote
+// it. Stored as a small TOML file next to the data (see `manifest_path`) together with the size
+// and hash of the data file, so a reload can tell whether the cache is stale.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueryManifest {
+    // ADQL statement, or whatever produced the export (e.g. a SIMBAD web query).
+    pub query: String,
+    // TAP endpoint the query ran against.
+    pub service: Option<String>,
+    // Free-form descriptions, e.g. "V < 6" or "ComponentPolicy::MergeSystems".
+    pub filters: Vec<String>,
+    pub crate_version: String,
+    pub format_version: u16,
+    pub data_file: String,
+    pub data_bytes: u64,
+    pub data_hash: u64
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ManifestIssue {
+    CrateVersion { recorded: String, current: String },
+    FormatVersion { recorded: u16, current: u16 },
+    // Size or hash of the data file differ from the manifest.
+    DataChanged
+}
+
+#[derive(Clone, Debug)]
+pub struct ManifestCheck {
+    pub manifest: QueryManifest,
+    pub issues: Vec<ManifestIssue>
+}
+
+impl ManifestCheck {
+    pub fn is_current(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+// "stars.bin" -> "stars.bin.manifest.toml".
+pub fn manifest_path<P: AsRef<Path>>(data: P) -> PathBuf {
+    let mut path = data.as_ref().as_os_str().to_owned();
+    path.push(".manifest.toml");
+    PathBuf::from(path)
+}
+
+impl QueryManifest {
+    pub fn new(query: &str) -> Self {
+        Self {
+            query: query.to_string(),
+            service: None,
+            filters: vec![],
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            format_version: FORMAT_VERSION,
+            data_file: String::new(),
+            data_bytes: 0,
+            data_hash: 0
+        }
+    }
+
+    pub fn service(mut self, url: &str) -> Self {
+        self.service = Some(url.to_string());
+        self
+    }
+
+    pub fn filter(mut self, description: &str) -> Self {
+        self.filters.push(description.to_string());
+        self
+    }
+
+    // Fingerprints the data file as it is now and writes the manifest next to it.
+    pub fn write_for<P: AsRef<Path>>(&self, data: P) -> Result<(), Box<dyn std::error::Error>> {
+        let data = data.as_ref();
+        let (data_bytes, data_hash) = fingerprint(data)?;
+        let manifest = Self {
+            data_file: data.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+            data_bytes,
+            data_hash,
+            ..self.clone()
+        };
+        std::fs::write(manifest_path(data), manifest.to_toml())?;
+        Ok(())
+    }
+
+    // Reads the manifest next to `data` and compares it with the file and this build.
+    pub fn check<P: AsRef<Path>>(data: P) -> Result<ManifestCheck, Box<dyn std::error::Error>> {
+        let data = data.as_ref();
+        let manifest = Self::from_toml(&std::fs::read_to_string(manifest_path(data))?)?;
+        let mut issues = vec![];
+        let current = env!("CARGO_PKG_VERSION");
+        if manifest.crate_version != current {
+            issues.push(ManifestIssue::CrateVersion { recorded: manifest.crate_version.clone(), current: current.to_string() });
+        }
+        if manifest.format_version != FORMAT_VERSION {
+            issues.push(ManifestIssue::FormatVersion { recorded: manifest.format_version, current: FORMAT_VERSION });
+        }
+        if fingerprint(data)? != (manifest.data_bytes, manifest.data_hash) {
+            issues.push(ManifestIssue::DataChanged);
+        }
+        Ok(ManifestCheck { manifest, issues })
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut toml = String::from("# Written by the simbad crate; describes how the data file next to it was produced.\n");
+        toml.push_str(&format!("query = {}\n", toml_string(&self.query)));
+        if let Some(service) = &self.service {
+            toml.push_str(&format!("service = {}\n", toml_string(service)));
+        }
+        toml.push_str(&format!("filters = [{}]\n", self.filters.iter().map(|f| toml_string(f)).collect::<Vec<_>>().join(", ")));
+        toml.push_str(&format!("crate_version = {}\n", toml_string(&self.crate_version)));
+        toml.push_str(&format!("format_version = {}\n", self.format_version));
+        toml.push_str(&format!("data_file = {}\n", toml_string(&self.data_file)));
+        toml.push_str(&format!("data_bytes = {}\n", self.data_bytes));
+        toml.push_str(&format!("data_hash = \"{:016x}\"\n", self.data_hash));
+        toml
+    }
+
+    // Reads what `to_toml` writes: one `key = value` per line with strings, integers and
+    // single-line string arrays. Unknown keys are ignored.
+    pub fn from_toml(toml: &str) -> Result<Self, SimbadError> {
+        let mut manifest = Self::new("");
+        let mut has_query = false;
+        for line in toml.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+            let (key, value) = line.split_once('=').ok_or(SimbadError::InvalidFormat)?;
+            let value = value.trim();
+            match key.trim() {
+                "query" => {
+                    manifest.query = parse_string(value)?.0;
+                    has_query = true;
+                }
+                "service" => manifest.service = Some(parse_string(value)?.0),
+                "filters" => manifest.filters = parse_string_array(value)?,
+                "crate_version" => manifest.crate_version = parse_string(value)?.0,
+                "format_version" => manifest.format_version = value.parse().map_err(|_| SimbadError::InvalidFormat)?,
+                "data_file" => manifest.data_file = parse_string(value)?.0,
+                "data_bytes" => manifest.data_bytes = value.parse().map_err(|_| SimbadError::InvalidFormat)?,
+                "data_hash" => manifest.data_hash = u64::from_str_radix(&parse_string(value)?.0, 16).map_err(|_| SimbadError::InvalidFormat)?,
+                _ => {}
+            }
+        }
+        if !has_query {
+            return Err(SimbadError::InvalidFormat);
+        }
+        Ok(manifest)
+    }
+}
+
+impl Catalog {
+    pub fn save_binary_with_manifest<P: AsRef<Path>>(&self, path: P, manifest: &QueryManifest) -> Result<(), Box<dyn std::error::Error>> {
+        self.save_binary(&path)?;
+        manifest.write_for(path)
+    }
+
+    // Loads the catalog even if the manifest reports issues; it's up to the caller whether a
+    // stale cache is refetched.
+    pub fn load_binary_checked<P: AsRef<Path>>(path: P) -> Result<(Self, ManifestCheck), Box<dyn std::error::Error>> {
+        let check = QueryManifest::check(&path)?;
+        Ok((Self::load_binary(path)?, check))
+    }
+}
+
+// Size and FNV-1a hash; stable across platforms and Rust versions, unlike `DefaultHasher`.
+fn fingerprint(path: &Path) -> std::io::Result<(u64, u64)> {
+    let mut r = BufReader::new(File::open(path)?);
+    let mut buffer = [0u8; 8192];
+    let (mut bytes, mut hash) = (0u64, 0xcbf2_9ce4_8422_2325u64);
+    loop {
+        let n = r.read(&mut buffer)?;
+        if n == 0 {
+            return Ok((bytes, hash));
+        }
+        bytes += n as u64;
+        for byte in &buffer[..n] {
+            hash = (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+fn toml_string(s: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c)
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// A basic string at the start of `input`, and what follows it.
+fn parse_string(input: &str) -> Result<(String, &str), SimbadError> {
+    let mut chars = input.strip_prefix('"').ok_or(SimbadError::InvalidFormat)?.char_indices();
+    let mut value = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, &input[i + 2..])),
+            '\\' => match chars.next().map(|(_, c)| c) {
+                Some('"') => value.push('"'),
+                Some('\\') => value.push('\\'),
+                Some('n') => value.push('\n'),
+                Some('r') => value.push('\r'),
+                Some('t') => value.push('\t'),
+                // Exactly four hex digits; `from_str_radix` alone would also take "+41".
+                Some('u') => {
+                    let hex = (0..4).map(|_| chars.next().map(|(_, c)| c).filter(char::is_ascii_hexdigit)).collect::<Option<String>>();
+                    let code = hex.and_then(|hex| u32::from_str_radix(&hex, 16).ok());
+                    value.push(code.and_then(char::from_u32).ok_or(SimbadError::InvalidFormat)?);
+                }
+                _ => return Err(SimbadError::InvalidFormat)
+            },
+            c => value.push(c)
+        }
+    }
+    Err(SimbadError::InvalidFormat)
+}
+
+fn parse_string_array(input: &str) -> Result<Vec<String>, SimbadError> {
+    let mut rest = input.strip_prefix('[').ok_or(SimbadError::InvalidFormat)?.trim_start();
+    let mut values = vec![];
+    loop {
+        if let Some(after) = rest.strip_prefix(']') {
+            return if after.trim().is_empty() { Ok(values) } else { Err(SimbadError::InvalidFormat) };
+        }
+        let (value, after) = parse_string(rest)?;
+        values.push(value);
+        let after = after.trim_start();
+        rest = match after.strip_prefix(',') {
+            Some(next) => next.trim_start(),
+            None if after.starts_with(']') => after,
+            None => return Err(SimbadError::InvalidFormat)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_escapes_and_empty_filters() {
+        let manifest = QueryManifest::new("SELECT * FROM basic WHERE main_id = '\"quoted\"'\n\tAND otype = 'a\\b' -- \u{1}")
+            .service("https://simbad.cds.unistra.fr/simbad/sim-tap");
+        assert!(manifest.filters.is_empty());
+        assert_eq!(QueryManifest::from_toml(&manifest.to_toml()).unwrap(), manifest);
+        let manifest = manifest.filter("V < 6").filter("name, \"with\" [brackets]");
+        assert_eq!(QueryManifest::from_toml(&manifest.to_toml()).unwrap(), manifest);
+    }
+
+    #[test]
+    fn reads_comments_and_unicode_escapes() {
+        let toml = "# written by hand\n\nquery = \"caf\\u00E9\"\n  # indented comment\nfilters = []\nextra = 1\n";
+        let manifest = QueryManifest::from_toml(toml).unwrap();
+        assert_eq!(manifest.query, "café");
+        assert!(manifest.filters.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        for toml in [
+            "service = \"x\"",
+            "query",
+            "query = unquoted",
+            "query = \"unterminated",
+            "query = \"\\u41\"",
+            "query = \"\\u+041\"",
+            "query = \"\\uD800\"",
+            "query = \"\\q\"",
+            "query = \"x\"\nfilters = [\"a\" \"b\"]",
+            "query = \"x\"\nfilters = [\"a\"",
+            "query = \"x\"\nfilters = [\"a\"] trailing",
+            "query = \"x\"\nformat_version = ten",
+            "query = \"x\"\ndata_hash = \"not hex\""
+        ] {
+            assert!(QueryManifest::from_toml(toml).is_err(), "{}", toml);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use glam::DVec3;
+use crate::{Distance, EquatorialCoordinate, SimbadError};
+
+const MAS_TO_RADIANS: f64 = std::f64::consts::PI / (180. * 3600. * 1000.);
+// km/s per (arcsec/yr * parsec).
+pub(crate) const KM_S_PER_AU_YR: f64 = 4.740470446;
+
+// Proper motion in mas/yr, with the RA component already multiplied by cos(dec) as SIMBAD exports it.
+#[cfg_attr(feature = "schemars", derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema))]
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub struct ProperMotion {
+    pub ra: f32,
+    pub dec: f32
+}
+
+impl ProperMotion {
+    pub fn new(ra: f32, dec: f32) -> Self {
+        Self { ra, dec }
+    }
+
+    // Velocity components in km/s at `distance` back to a proper motion.
+    pub fn from_tangential_velocity(ra_km_s: f32, dec_km_s: f32, distance: Distance) -> Self {
+        let per_mas = (KM_S_PER_AU_YR * distance.parsecs() as f64 / 1000.) as f32;
+        Self::new(ra_km_s / per_mas, dec_km_s / per_mas)
+    }
+
+    // Total proper motion in mas/yr.
+    pub fn total(&self) -> f32 {
+        self.ra.hypot(self.dec)
+    }
+
+    // Direction of motion in radians, from north through east.
+    pub fn position_angle(&self) -> f32 {
+        self.ra.atan2(self.dec).rem_euclid(std::f32::consts::TAU)
+    }
+
+    // Speed across the line of sight in km/s, 4.74 μ[″/yr] d[pc].
+    pub fn tangential_velocity(&self, distance: Distance) -> f32 {
+        (KM_S_PER_AU_YR * self.total() as f64 / 1000. * distance.parsecs() as f64) as f32
+    }
+
+    // Moves `coord` along the great circle given by the motion, which stays well-behaved near the poles.
+    pub fn apply(&self, coord: &EquatorialCoordinate, years: f64) -> EquatorialCoordinate {
+        let (sin_ra, cos_ra) = (coord.right_ascension as f64).sin_cos();
+        let (sin_dec, cos_dec) = (coord.declination as f64).sin_cos();
+        let pos = DVec3::new(cos_dec * cos_ra, cos_dec * sin_ra, sin_dec);
+        let east = DVec3::new(-sin_ra, cos_ra, 0.);
+        let north = DVec3::new(-sin_dec * cos_ra, -sin_dec * sin_ra, cos_dec);
+        let offset = (east * self.ra as f64 + north * self.dec as f64) * MAS_TO_RADIANS * years;
+        let moved = (pos + offset).normalize();
+        EquatorialCoordinate::new(moved.y.atan2(moved.x) as f32, moved.z.clamp(-1., 1.).asin() as f32)
+    }
+}
+
+// "pmra pmdec" in mas/yr, the layout `FromStr` reads; a precision applies to both components.
+impl Display for ProperMotion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*} {:.*}", precision, self.ra, precision, self.dec),
+            None => write!(f, "{} {}", self.ra, self.dec)
+        }
+    }
+}
+
+impl FromStr for ProperMotion {
+    type Err = SimbadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut splits = s.split_whitespace().map(|v| v.parse::<f32>());
+        match (splits.next(), splits.next()) {
+            (Some(Ok(ra)), Some(Ok(dec))) => Ok(Self::new(ra, dec)),
+            _ => Err(SimbadError::InvalidProperMotion)
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use std::path::Path;
+use crate::{read_records, record_coordinate, record_identifiers, resolve_parallax_unit, stars_from_records};
+use crate::{Band, ColumnSet, Distance, EquatorialCoordinate, Identifier, ImportOptions, ImportReport, ImportWarning, Photometry, Provenance, Record, Source, Star, StellarPosition};
+
+// Broad class of a SIMBAD object type (OTYPE).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ObjectKind {
+    Star,
+    Galaxy,
+    Nebula,
+    Cluster,
+    Other
+}
+
+impl ObjectKind {
+    // Takes the short OTYPE codes SIMBAD exports ("PM*", "SyG", "HII", "OpC", ...). Codes ending in
+    // '*' are stars, apart from the star groupings and supernovae listed explicitly.
+    pub fn from_otype(otype: &str) -> Self {
+        match otype.trim() {
+            "Cl*" | "GlC" | "OpC" | "As*" | "St*" | "MGr" | "ClG" | "GrG" | "CGG" | "SCG" => Self::Cluster,
+            "G" | "G?" | "GiG" | "GiC" | "GiP" | "BiC" | "IG" | "PaG" | "SBG" | "H2G" | "EmG" | "LSB" | "rG" | "AGN" | "LIN"
+            | "SyG" | "Sy1" | "Sy2" | "Bla" | "BLL" | "OVV" | "QSO" | "AG?" | "Q?" | "Bz?" | "BL?" => Self::Galaxy,
+            "PN" | "PN?" | "HII" | "RNe" | "DNe" | "GNe" | "BNe" | "SNR" | "SR?" | "ISM" | "MoC" | "Cld" | "glb" | "HVC"
+            | "cor" | "bub" | "EmO" | "CGb" => Self::Nebula,
+            "SN*" => Self::Other,
+            otype if otype.ends_with('*') || otype.ends_with("*?") => Self::Star,
+            _ => Self::Other
+        }
+    }
+}
+
+// Sky position and, where known, distance of any catalog object.
+pub trait Positioned {
+    fn coordinate(&self) -> EquatorialCoordinate;
+    // Light-years.
+    fn distance_ly(&self) -> Option<f32>;
+}
+
+pub trait Photometric {
+    fn photometry(&self) -> &Photometry;
+
+    fn magnitude(&self, band: Band) -> Option<f32> {
+        self.photometry().get(band)
+    }
+}
+
+// A galaxy, nebula, cluster or anything else that isn't a single star.
+#[derive(Clone, Debug)]
+pub struct ExtendedObject {
+    pub id: usize,
+    pub identifier: String,
+    pub aliases: Vec<Identifier>,
+    pub name: String,
+    // SIMBAD OTYPE, e.g. "SyG" or "OpC".
+    pub object_type: String,
+    pub coord: EquatorialCoordinate,
+    // Light-years; only set when SIMBAD has a parallax.
+    pub distance: Option<f32>,
+    pub photometry: Photometry,
+    // As exported, e.g. "SA(s)b" and "190.55 61.66 35".
+    pub morphology: Option<String>,
+    pub angular_size: Option<String>,
+    pub redshift: Option<f32>,
+    pub radial_velocity: Option<f32>,
+    pub provenance: Vec<Provenance>
+}
+
+#[derive(Clone, Debug)]
+pub enum AstronomicalObject {
+    Star(Star),
+    Galaxy(ExtendedObject),
+    Nebula(ExtendedObject),
+    Cluster(ExtendedObject),
+    Other(ExtendedObject)
+}
+
+impl AstronomicalObject {
+    pub fn kind(&self) -> ObjectKind {
+        match self {
+            Self::Star(_) => ObjectKind::Star,
+            Self::Galaxy(_) => ObjectKind::Galaxy,
+            Self::Nebula(_) => ObjectKind::Nebula,
+            Self::Cluster(_) => ObjectKind::Cluster,
+            Self::Other(_) => ObjectKind::Other
+        }
+    }
+
+    pub fn id(&self) -> usize {
+        match self {
+            Self::Star(star) => star.id,
+            Self::Galaxy(o) | Self::Nebula(o) | Self::Cluster(o) | Self::Other(o) => o.id
+        }
+    }
+
+    pub fn identifier(&self) -> &str {
+        match self {
+            Self::Star(star) => &star.identifier,
+            Self::Galaxy(o) | Self::Nebula(o) | Self::Cluster(o) | Self::Other(o) => &o.identifier
+        }
+    }
+
+    pub fn as_star(&self) -> Option<&Star> {
+        match self {
+            Self::Star(star) => Some(star),
+            _ => None
+        }
+    }
+
+    pub fn as_extended(&self) -> Option<&ExtendedObject> {
+        match self {
+            Self::Star(_) => None,
+            Self::Galaxy(o) | Self::Nebula(o) | Self::Cluster(o) | Self::Other(o) => Some(o)
+        }
+    }
+}
+
+impl Positioned for Star {
+    fn coordinate(&self) -> EquatorialCoordinate {
+        StellarPosition::from(self.pos).coord
+    }
+
+    fn distance_ly(&self) -> Option<f32> {
+        Some(self.distance()).filter(|d| *d > 0.)
+    }
+}
+
+impl Photometric for Star {
+    fn photometry(&self) -> &Photometry {
+        &self.photometry
+    }
+}
+
+impl Positioned for ExtendedObject {
+    fn coordinate(&self) -> EquatorialCoordinate {
+        self.coord
+    }
+
+    fn distance_ly(&self) -> Option<f32> {
+        self.distance
+    }
+}
+
+impl Photometric for ExtendedObject {
+    fn photometry(&self) -> &Photometry {
+        &self.photometry
+    }
+}
+
+impl Positioned for AstronomicalObject {
+    fn coordinate(&self) -> EquatorialCoordinate {
+        match self {
+            Self::Star(star) => star.coordinate(),
+            Self::Galaxy(o) | Self::Nebula(o) | Self::Cluster(o) | Self::Other(o) => o.coordinate()
+        }
+    }
+
+    fn distance_ly(&self) -> Option<f32> {
+        match self {
+            Self::Star(star) => star.distance_ly(),
+            Self::Galaxy(o) | Self::Nebula(o) | Self::Cluster(o) | Self::Other(o) => o.distance_ly()
+        }
+    }
+}
+
+impl Photometric for AstronomicalObject {
+    fn photometry(&self) -> &Photometry {
+        match self {
+            Self::Star(star) => &star.photometry,
+            Self::Galaxy(o) | Self::Nebula(o) | Self::Cluster(o) | Self::Other(o) => &o.photometry
+        }
+    }
+}
+
+pub fn import_objects<P: AsRef<Path>>(path: P) -> Result<Vec<AstronomicalObject>, Box<dyn std::error::Error>> {
+    Ok(import_objects_with_options(path, &ImportOptions::default())?.records)
+}
+
+// Like `import_with_options`, but keeps the rows whose OTYPE isn't a star as extended objects
+// instead of dropping them. Stars go through the same pipeline (and options) as `import`; the
+// result is in file order.
+pub fn import_objects_with_options<P: AsRef<Path>>(path: P, options: &ImportOptions) -> Result<ImportReport<AstronomicalObject>, Box<dyn std::error::Error>> {
+    let source = Source::File(path.as_ref().to_path_buf());
+    let ImportReport { records, skipped, mut warnings } = read_records(path, options, ColumnSet::All)?;
+    let records = records.into_iter().filter_map(|record| options.apply_record(record)).collect::<Vec<_>>();
+    let parallax_unit = resolve_parallax_unit(options, &warnings);
+    let (stars, others): (Vec<Record>, Vec<Record>) = records.into_iter().partition(|r| ObjectKind::from_otype(r.object_type()) == ObjectKind::Star);
+    let mut objects = stars_from_records(stars, &source, parallax_unit, options, &mut warnings)?
+        .into_iter()
+        .map(AstronomicalObject::Star)
+        .collect::<Vec<_>>();
+    for record in others {
+        let Ok(coord) = record_coordinate(&record, &mut warnings) else {
+            warnings.push(ImportWarning::MissingCoordinates { id: record.id, line: record.line });
+            continue;
+        };
+        let (identifier, aliases) = record_identifiers(&record);
+        let distance = record.plx.map(|plx| parallax_unit.to_arcseconds(plx)).filter(|plx| *plx > 0.).map(|plx| Distance::from_parsecs(1. / plx).light_years());
+        let object = ExtendedObject {
+            id: record.id,
+            identifier,
+            aliases,
+            name: record.pretty_name.as_deref().map(crate::identifier::normalize_whitespace).unwrap_or_default(),
+            object_type: record.typ.clone(),
+            coord,
+            distance,
+            photometry: Photometry::from(&record),
+            morphology: record.morph_type.clone(),
+            angular_size: record.ang_size.clone(),
+            redshift: record.redshift,
+            radial_velocity: record.radvel,
+            provenance: vec![Provenance::new(source.clone(), Some(record.line))],
+        };
+        objects.push(match ObjectKind::from_otype(&record.typ) {
+            ObjectKind::Galaxy => AstronomicalObject::Galaxy(object),
+            ObjectKind::Nebula => AstronomicalObject::Nebula(object),
+            ObjectKind::Cluster => AstronomicalObject::Cluster(object),
+            _ => AstronomicalObject::Other(object)
+        });
+    }
+    objects.sort_by_key(|object| {
+        let provenance = match object {
+            AstronomicalObject::Star(star) => &star.provenance,
+            AstronomicalObject::Galaxy(o) | AstronomicalObject::Nebula(o) | AstronomicalObject::Cluster(o) | AstronomicalObject::Other(o) => &o.provenance
+        };
+        provenance.first().and_then(|p| p.row)
+    });
+    Ok(ImportReport { records: objects, skipped, warnings })
+}
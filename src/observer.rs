@@ -0,0 +1,40 @@
+use glam::DVec3;
+use crate::Angle;
+use crate::ephemeris::J2000;
+
+const EARTH_RADIUS: f64 = 4.2635e-5; // AU
+pub(crate) const SIDEREAL_RATE: f64 = std::f64::consts::TAU * 1.00273790935; // radians per day
+
+#[derive(Default, Copy, Clone, Debug)]
+pub struct Observer {
+    pub latitude: f32,
+    pub longitude: f32,
+}
+
+impl Observer {
+    pub fn new(latitude: f32, longitude: f32) -> Self {
+        Self {
+            latitude,
+            longitude,
+        }
+    }
+
+    pub fn local_sidereal_time(&self, jd: f64) -> f64 {
+        (greenwich_sidereal_time(jd) + self.longitude as f64).wrap_positive()
+    }
+
+    pub(crate) fn geocentric_position(&self, jd: f64) -> DVec3 {
+        let lst = self.local_sidereal_time(jd);
+        let lat = self.latitude as f64;
+        DVec3::new(lat.cos() * lst.cos(), lat.cos() * lst.sin(), lat.sin()) * EARTH_RADIUS
+    }
+
+    pub(crate) fn geocentric_velocity(&self, jd: f64) -> DVec3 {
+        let pos = self.geocentric_position(jd);
+        DVec3::new(-pos.y, pos.x, 0.) * SIDEREAL_RATE
+    }
+}
+
+pub fn greenwich_sidereal_time(jd: f64) -> f64 {
+    (280.46061837 + 360.98564736629 * (jd - J2000)).to_radians().wrap_positive()
+}
@@ -0,0 +1,88 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use crate::{Band, Observer, Star, StellarPosition};
+use crate::visibility::night_window;
+
+// Identifier SkySafari can resolve: HIP, then HD, then whatever SIMBAD had as main identifier.
+fn catalog_number(star: &Star) -> String {
+    ["HIP", "HD"].into_iter()
+        .find_map(|catalog| star.id_in_catalog(catalog).map(|id| format!("{} {}", catalog, id)))
+        .unwrap_or_else(|| star.identifier.trim_start_matches('*').trim().to_string())
+}
+
+pub fn export_skylist<'a, I: IntoIterator<Item = &'a Star>, P: AsRef<Path>>(stars: I, path: P) -> Result<(), Box<dyn std::error::Error>> {
+    let mut w = BufWriter::new(File::create(path)?);
+    write_skylist(stars, &mut w)?;
+    w.flush()?;
+    Ok(())
+}
+
+// SkySafari observing list (.skylist). SkySafari looks the objects up in its own database by
+// catalog number and name, so positions are not written.
+pub fn write_skylist<'a, I: IntoIterator<Item = &'a Star>, W: Write>(stars: I, mut w: W) -> std::io::Result<()> {
+    writeln!(w, "SkySafariObservingListVersion=3.0")?;
+    writeln!(w, "SortedBy=Default Order")?;
+    for star in stars {
+        writeln!(w, "SkyObject=BeginObject")?;
+        writeln!(w, "   ObjectID=2,-1,-1")?;
+        let number = catalog_number(star);
+        if !number.is_empty() {
+            writeln!(w, "   CatalogNumber={}", number)?;
+        }
+        if !star.name.is_empty() {
+            writeln!(w, "   CommonName={}", star.name)?;
+        }
+        writeln!(w, "EndObject=SkyObject")?;
+    }
+    Ok(())
+}
+
+pub fn export_target_list<'a, I: IntoIterator<Item = &'a Star>, P: AsRef<Path>>(stars: I, observer: &Observer, date: f64, step_hours: f64, path: P) -> Result<(), Box<dyn std::error::Error>> {
+    let mut w = BufWriter::new(File::create(path)?);
+    write_target_list(stars, observer, date, step_hours, &mut w)?;
+    w.flush()?;
+    Ok(())
+}
+
+// Target list for the night starting after `date` (Julian date, e.g. local noon): position,
+// V magnitude, transit and rise/set times, plus altitude and azimuth in degrees every
+// `step_hours` through the night. Times are Julian dates; the ephemeris columns are named
+// after the time they refer to ("alt@2460000.6250").
+pub fn write_target_list<'a, I: IntoIterator<Item = &'a Star>, W: Write>(stars: I, observer: &Observer, date: f64, step_hours: f64, w: W) -> Result<(), Box<dyn std::error::Error>> {
+    let (start, end) = night_window(observer, date).unwrap_or((date, date + 1.));
+    let step = step_hours.max(1. / 60.) / 24.;
+    let times = (0..).map(|i| start + i as f64 * step).take_while(|t| *t <= end).collect::<Vec<_>>();
+    let mut wtr = csv::Writer::from_writer(w);
+    let mut header = ["identifier", "name", "ra [deg]", "dec [deg]", "mag_v", "transit", "rise", "set", "max_alt [deg]"]
+        .map(String::from).to_vec();
+    for t in &times {
+        header.push(format!("alt@{:.4}", t));
+        header.push(format!("az@{:.4}", t));
+    }
+    wtr.write_record(&header)?;
+    for star in stars {
+        let coord = StellarPosition::from(star.pos).coord;
+        let transit = coord.transit_after(observer, start);
+        let rise_set = coord.rise_set(observer, transit, 0.);
+        let mut row = vec![
+            star.identifier.clone(),
+            star.name.clone(),
+            coord.right_ascension.to_degrees().to_string(),
+            coord.declination.to_degrees().to_string(),
+            star.photometry.get(Band::V).map(|v| v.to_string()).unwrap_or_default(),
+            format!("{:.5}", transit),
+            rise_set.map(|(rise, _)| format!("{:.5}", rise)).unwrap_or_default(),
+            rise_set.map(|(_, set)| format!("{:.5}", set)).unwrap_or_default(),
+            format!("{:.2}", coord.culmination_altitude(observer).to_degrees())
+        ];
+        for t in &times {
+            let horizontal = coord.to_horizontal(observer, *t);
+            row.push(format!("{:.2}", horizontal.altitude.to_degrees()));
+            row.push(format!("{:.2}", horizontal.azimuth.to_degrees()));
+        }
+        wtr.write_record(&row)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
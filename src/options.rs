@@ -0,0 +1,197 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex, PoisonError};
+use crate::{Record, Star, StarKey};
+
+// Nearest known star (Proxima Centauri) is at 0.768", anything larger is not a real parallax.
+const MAX_PLAUSIBLE_PARALLAX: f32 = 0.8;
+
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub enum ParallaxUnit {
+    #[default]
+    Milliarcseconds,
+    Arcseconds,
+    Auto
+}
+
+impl ParallaxUnit {
+    pub fn to_arcseconds(&self, value: f32) -> f32 {
+        match self {
+            Self::Arcseconds => value,
+            Self::Milliarcseconds | Self::Auto => value / 1000.
+        }
+    }
+
+    // Unit named by a parallax header cell, e.g. "plx (arcsec)", "plx [mas]" or the TAP "plx_value"
+    // (always mas). The values themselves can't tell: a milliarcsecond export of distant stars
+    // looks just like an arcsecond one, so `Auto` without a hint falls back to SIMBAD's mas.
+    pub fn from_column(column: &str) -> Option<Self> {
+        let column = column.trim();
+        if column.eq_ignore_ascii_case("plx_value") {
+            return Some(Self::Milliarcseconds);
+        }
+        let (_, unit) = column.rsplit_once(['(', '['])?;
+        match unit.trim_end_matches([')', ']']).trim().to_ascii_lowercase().as_str() {
+            "mas" | "milliarcsec" | "milliarcseconds" => Some(Self::Milliarcseconds),
+            "as" | "arcsec" | "arcseconds" | "\"" => Some(Self::Arcseconds),
+            _ => None
+        }
+    }
+
+    pub(crate) fn is_plausible(arcseconds: f32) -> bool {
+        arcseconds > 0. && arcseconds <= MAX_PLAUSIBLE_PARALLAX
+    }
+}
+
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub enum ComponentPolicy {
+    KeepAll,
+    #[default]
+    PrimaryOnly,
+    MergeSystems
+}
+
+// Which columns are deserialized into each `Record`.
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub enum ColumnSet {
+    #[default]
+    All,
+    // Only what star import uses, via `SlimRecord`. Imports the same stars as `All`, but
+    // `Record::extra` stays empty and the other coordinate columns are only kept when coord1
+    // has no position.
+    Minimal
+}
+
+// How strictly numeric fields are read.
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub enum NumberFormat {
+    #[default]
+    Strict,
+    // Also accepts a decimal comma ("0,742") and values wrapped in quotes or stray whitespace, as
+    // left behind by spreadsheet round trips in European locales.
+    Tolerant
+}
+
+// Shared so `ImportOptions` stays `Clone`, and behind a mutex so options built on one thread can
+// run an import on another.
+type Hook<T> = Arc<Mutex<dyn FnMut(T) -> Option<T> + Send>>;
+
+#[derive(Default, Clone)]
+pub struct ImportOptions {
+    pub(crate) parallax_unit: ParallaxUnit,
+    pub(crate) component_policy: ComponentPolicy,
+    pub(crate) columns: ColumnSet,
+    pub(crate) numbers: NumberFormat,
+    // Data rows to pass over and the most to read after them.
+    pub(crate) skip: usize,
+    pub(crate) limit: Option<usize>,
+    // Drop rows without a spectral type, as `import` used to; otherwise their `class` is
+    // `UNKNOWN_SPECTRAL_TYPE`.
+    pub(crate) require_spectral_type: bool,
+    map_record: Option<Hook<Record>>,
+    map_star: Option<Hook<Star>>
+}
+
+impl Debug for ImportOptions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImportOptions")
+            .field("parallax_unit", &self.parallax_unit)
+            .field("component_policy", &self.component_policy)
+            .field("columns", &self.columns)
+            .field("numbers", &self.numbers)
+            .field("skip", &self.skip)
+            .field("limit", &self.limit)
+            .field("require_spectral_type", &self.require_spectral_type)
+            .field("map_record", &self.map_record.is_some())
+            .field("map_star", &self.map_star.is_some())
+            .finish()
+    }
+}
+
+impl ImportOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parallax_unit(mut self, unit: ParallaxUnit) -> Self {
+        self.parallax_unit = unit;
+        self
+    }
+
+    pub fn component_policy(mut self, policy: ComponentPolicy) -> Self {
+        self.component_policy = policy;
+        self
+    }
+
+    pub fn columns(mut self, columns: ColumnSet) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    pub fn numbers(mut self, numbers: NumberFormat) -> Self {
+        self.numbers = numbers;
+        self
+    }
+
+    // Both count data rows of the file, malformed ones included, before any filtering. Reading
+    // stops once `limit` rows are through, so the rest of the file is never read past the CSV
+    // reader's buffer, let alone deserialized.
+    pub fn skip(mut self, rows: usize) -> Self {
+        self.skip = rows;
+        self
+    }
+
+    pub fn limit(mut self, rows: usize) -> Self {
+        self.limit = Some(rows);
+        self
+    }
+
+    pub fn require_spectral_type(mut self, require: bool) -> Self {
+        self.require_spectral_type = require;
+        self
+    }
+
+    // Runs on every parsed row before it is turned into a star; returning None drops the row.
+    pub fn map_record(mut self, hook: impl FnMut(Record) -> Option<Record> + Send + 'static) -> Self {
+        self.map_record = Some(Arc::new(Mutex::new(hook)));
+        self
+    }
+
+    // Runs on every star before duplicate detection and system merging; returning None drops it.
+    pub fn map_star(mut self, hook: impl FnMut(Star) -> Option<Star> + Send + 'static) -> Self {
+        self.map_star = Some(Arc::new(Mutex::new(hook)));
+        self
+    }
+
+    pub(crate) fn apply_record(&self, record: Record) -> Option<Record> {
+        match &self.map_record {
+            Some(hook) => (hook.lock().unwrap_or_else(PoisonError::into_inner))(record),
+            None => Some(record)
+        }
+    }
+
+    pub(crate) fn apply_star(&self, star: Star) -> Option<Star> {
+        match &self.map_star {
+            Some(hook) => (hook.lock().unwrap_or_else(PoisonError::into_inner))(star),
+            None => Some(star)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImportWarning {
+    ParallaxUnitDetected(ParallaxUnit),
+    ImplausibleParallax { id: usize, parallax: f32 },
+    // Lines before the header row and non-data lines after it (separators, blank lines, row
+    // count footers) that were skipped. With a `limit` only the lines up to where reading stopped
+    // are counted.
+    IgnoredLines { preamble: usize, trailing: usize },
+    // A later row with the same `StarKey` as an already imported star; the later row is dropped.
+    DuplicateStar { id: usize, key: StarKey },
+    // Position taken from the galactic coord4 column, which SIMBAD only gives to 0.01°.
+    GalacticCoordinatesOnly { id: usize },
+    // A row none of whose coordinate columns parse; it is left out of the import.
+    MissingCoordinates { id: usize, line: u64 },
+    // Header cells no `RecordSchema` maps to a `Record` field; their values end up in
+    // `Record::extra`.
+    UnknownColumns(Vec<String>)
+}
@@ -0,0 +1,266 @@
+use std::fmt::{Display, Formatter};
+use crate::{EquatorialCoordinate, StellarPosition, J2000};
+
+// Unit of the right ascension in text: hours in catalogs, degrees in most computations.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RaUnit {
+    #[default]
+    Hours,
+    Degrees
+}
+
+// How `EquatorialCoordinate::format` writes a position; declination is always in degrees.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DisplayStyle {
+    // "06 45 08.917 -16 42 58.02", as SIMBAD exports it. Also what `Display` uses.
+    #[default]
+    Sexagesimal,
+    // "6.7524769 -16.7161167"
+    DecimalHours,
+    // "101.2871532 -16.7161167"
+    DecimalDegrees,
+    // "06h45m08.92s −16°42′58.0″", for people rather than parsers.
+    Symbols
+}
+
+// Reference frame named by `format_with`; the coordinate itself is not transformed.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CoordinateFrame {
+    #[default]
+    Icrs,
+    Fk5,
+    // Epochs are written as Besselian years.
+    Fk4
+}
+
+impl CoordinateFrame {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Icrs => "ICRS",
+            Self::Fk5 => "FK5",
+            Self::Fk4 => "FK4"
+        }
+    }
+}
+
+// Julian date as "J2000", "J2016.5" or, for FK4, "B1950".
+pub fn format_epoch(jd: f64, frame: CoordinateFrame) -> String {
+    let (prefix, year) = match frame {
+        CoordinateFrame::Fk4 => ('B', 1900. + (jd - 2415020.31352) / 365.242198781),
+        _ => ('J', 2000. + (jd - J2000) / 365.25)
+    };
+    // Rounded to three decimals so J2000 itself doesn't print as "J2000.0000000001".
+    format!("{}{}", prefix, (year * 1000.).round() / 1000.)
+}
+
+// `index` is the position of the offending whitespace separated token, counting from 0.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CoordParseError {
+    MissingToken { index: usize, expected: &'static str },
+    InvalidToken { index: usize, token: String, reason: &'static str },
+    TrailingInput { index: usize, token: String }
+}
+
+impl Display for CoordParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingToken { index, expected } => write!(f, "missing {} at token {}", expected, index),
+            Self::InvalidToken { index, token, reason } => write!(f, "invalid token {} \"{}\": {}", index, token, reason),
+            Self::TrailingInput { index, token } => write!(f, "unexpected token {} \"{}\"", index, token)
+        }
+    }
+}
+
+impl std::error::Error for CoordParseError {}
+
+// Parses "06 45 08.917 -16 42 58.02" as well as the "06:45:08.9 -16:42:58" and
+// "06h45m08.9s -16d42m58s" spellings. Seconds are optional on either side as long as the
+// declination carries an explicit sign.
+pub fn parse_sexagesimal(input: &str) -> Result<EquatorialCoordinate, CoordParseError> {
+    parse_sexagesimal_in(input, RaUnit::Hours)
+}
+
+// Either layout, two plain numbers or sexagesimal, with the right ascension in `ra_unit`. The
+// unit is never guessed: "101 17 13.8" is 101 hours and fails when read as hours.
+pub fn parse_equatorial(input: &str, ra_unit: RaUnit) -> Result<EquatorialCoordinate, CoordParseError> {
+    let tokens = input.split_whitespace().collect::<Vec<_>>();
+    if tokens.len() == 2 && tokens.iter().all(|t| t.parse::<f32>().is_ok()) {
+        let coord = parse_decimal_degrees(input)?;
+        return Ok(match ra_unit {
+            RaUnit::Degrees => coord,
+            RaUnit::Hours => {
+                let hours = coord.right_ascension.to_degrees();
+                if hours >= 24. {
+                    return Err(CoordParseError::InvalidToken { index: 0, token: tokens[0].to_string(), reason: "hours must be below 24" });
+                }
+                EquatorialCoordinate::new((hours * 15.).to_radians(), coord.declination)
+            }
+        });
+    }
+    parse_sexagesimal_in(input, ra_unit)
+}
+
+fn parse_sexagesimal_in(input: &str, ra_unit: RaUnit) -> Result<EquatorialCoordinate, CoordParseError> {
+    let normalized = input.replace('−', "-").chars().map(|c| if "hdms:°′″'\"".contains(c) { ' ' } else { c }).collect::<String>();
+    let tokens = normalized.split_whitespace().collect::<Vec<_>>();
+    let dec_start = tokens.iter().position(|t| t.starts_with(['+', '-'])).unwrap_or(3.min(tokens.len()));
+    let (names, limit, scale) = match ra_unit {
+        RaUnit::Hours => (["hours", "minutes", "seconds"], 24, 15.),
+        RaUnit::Degrees => (["degrees", "arcminutes", "arcseconds"], 360, 1.)
+    };
+    let (ra_negative, ra) = sexagesimal(&tokens[..dec_start], 0, names, limit)?;
+    if ra_negative {
+        return Err(CoordParseError::InvalidToken { index: 0, token: tokens[0].to_string(), reason: "right ascension can't be negative" });
+    }
+    let dec_tokens = &tokens[dec_start..(dec_start + 3).min(tokens.len())];
+    let (dec_negative, degrees) = sexagesimal(dec_tokens, dec_start, ["degrees", "arcminutes", "arcseconds"], 90)?;
+    if degrees > 90. {
+        return Err(CoordParseError::InvalidToken { index: dec_start, token: tokens[dec_start].to_string(), reason: "declination beyond ±90°" });
+    }
+    if let Some(extra) = tokens.get(dec_start + dec_tokens.len()) {
+        return Err(CoordParseError::TrailingInput { index: dec_start + dec_tokens.len(), token: extra.to_string() });
+    }
+    let dec = if dec_negative { -degrees } else { degrees };
+    Ok(EquatorialCoordinate::new((ra * scale).to_radians(), dec.to_radians()))
+}
+
+// An equatorial column of an export: sexagesimal as SIMBAD writes by default, or decimal degrees
+// when it holds just two numbers (SIMBAD's decimal output option, and TAP's joined ra/dec), which
+// `parse_sexagesimal` would otherwise take for hours and degrees.
+pub(crate) fn parse_coordinate_column(input: &str) -> Result<EquatorialCoordinate, CoordParseError> {
+    if input.split_whitespace().count() == 2 {
+        parse_decimal_degrees(input)
+    } else {
+        parse_sexagesimal(input)
+    }
+}
+
+// Parses "101.2872 -16.7161": right ascension and declination in decimal degrees.
+pub fn parse_decimal_degrees(input: &str) -> Result<EquatorialCoordinate, CoordParseError> {
+    let tokens = input.split_whitespace().collect::<Vec<_>>();
+    let number = |index: usize, expected: &'static str| -> Result<f32, CoordParseError> {
+        let token = tokens.get(index).ok_or(CoordParseError::MissingToken { index, expected })?;
+        token.parse::<f32>().ok().filter(|v| v.is_finite()).ok_or_else(|| CoordParseError::InvalidToken { index, token: token.to_string(), reason: "not a number" })
+    };
+    let ra = number(0, "right ascension")?;
+    let dec = number(1, "declination")?;
+    if !(-90. ..=90.).contains(&dec) {
+        return Err(CoordParseError::InvalidToken { index: 1, token: tokens[1].to_string(), reason: "declination beyond ±90°" });
+    }
+    if let Some(extra) = tokens.get(2) {
+        return Err(CoordParseError::TrailingInput { index: 2, token: extra.to_string() });
+    }
+    Ok(EquatorialCoordinate::new(ra.to_radians(), dec.to_radians()))
+}
+
+// Inverse of `parse_sexagesimal` in SIMBAD's layout, "06 45 08.917 -16 42 58.02": milliseconds of
+// time and centiarcseconds.
+pub fn format_sexagesimal(coord: &EquatorialCoordinate) -> String {
+    let ms = ((coord.right_ascension as f64).rem_euclid(std::f64::consts::TAU).to_degrees() / 15. * 3.6e6).round() as u64 % 86_400_000;
+    let cas = ((coord.declination as f64).to_degrees().abs() * 3.6e5).round() as u64;
+    let sign = if coord.declination < 0. && cas > 0 { '-' } else { '+' };
+    format!(
+        "{:02} {:02} {:02}.{:03} {}{:02} {:02} {:02}.{:02}",
+        ms / 3_600_000, ms / 60_000 % 60, ms / 1000 % 60, ms % 1000,
+        sign, cas / 360_000, cas / 6000 % 60, cas / 100 % 60, cas % 100
+    )
+}
+
+impl EquatorialCoordinate {
+    pub fn ra_hours(&self) -> f32 {
+        self.right_ascension.to_degrees() / 15.
+    }
+
+    pub fn ra_degrees(&self) -> f32 {
+        self.right_ascension.to_degrees()
+    }
+
+    pub fn dec_degrees(&self) -> f32 {
+        self.declination.to_degrees()
+    }
+
+    pub fn format(&self, style: DisplayStyle) -> String {
+        match style {
+            DisplayStyle::Sexagesimal => format_sexagesimal(self),
+            DisplayStyle::DecimalHours => format!("{:.7} {:+.7}", self.ra_hours(), self.dec_degrees()),
+            DisplayStyle::DecimalDegrees => format!("{:.7} {:+.7}", self.ra_degrees(), self.dec_degrees()),
+            DisplayStyle::Symbols => {
+                let cs = ((self.right_ascension as f64).rem_euclid(std::f64::consts::TAU).to_degrees() / 15. * 3.6e5).round() as u64 % 8_640_000;
+                let das = ((self.declination as f64).to_degrees().abs() * 36_000.).round() as u64;
+                let sign = if self.declination < 0. && das > 0 { '−' } else { '+' };
+                format!(
+                    "{:02}h{:02}m{:02}.{:02}s {}{:02}°{:02}′{:02}.{}″",
+                    cs / 360_000, cs / 6000 % 60, cs / 100 % 60, cs % 100,
+                    sign, das / 36_000, das / 600 % 60, das / 10 % 60, das % 10
+                )
+            }
+        }
+    }
+
+    // e.g. "ICRS 06h45m08.92s −16°42′58.0″ (J2000)", for logs and UIs. `epoch` is a Julian date.
+    pub fn format_with(&self, frame: CoordinateFrame, epoch: f64, style: DisplayStyle) -> String {
+        format!("{} {} ({})", frame.label(), self.format(style), format_epoch(epoch, frame))
+    }
+}
+
+impl StellarPosition {
+    // The direction only, as `EquatorialCoordinate::format_with`.
+    pub fn format_with(&self, frame: CoordinateFrame, epoch: f64, style: DisplayStyle) -> String {
+        self.coord.format_with(frame, epoch, style)
+    }
+}
+
+impl Display for EquatorialCoordinate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.format(DisplayStyle::Sexagesimal))
+    }
+}
+
+// Whole units, minutes and optional seconds starting at token `offset`; returns the sign and
+// the unsigned value in whole units.
+fn sexagesimal(tokens: &[&str], offset: usize, names: [&'static str; 3], limit: u32) -> Result<(bool, f32), CoordParseError> {
+    let first = tokens.first().ok_or(CoordParseError::MissingToken { index: offset, expected: names[0] })?;
+    let (negative, whole) = match first.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, first.strip_prefix('+').unwrap_or(first))
+    };
+    let invalid = |index: usize, reason: &'static str| CoordParseError::InvalidToken { index: offset + index, token: tokens[index].to_string(), reason };
+    let whole = whole.parse::<u32>().map_err(|_| invalid(0, "expected an unsigned integer"))?;
+    // Right ascension wraps, so its upper limit is exclusive.
+    if whole > limit || (whole == limit && limit != 90) {
+        return Err(invalid(0, "out of range"));
+    }
+    let minutes = tokens.get(1).ok_or(CoordParseError::MissingToken { index: offset + 1, expected: names[1] })?;
+    let minutes = minutes.parse::<u8>().ok().filter(|m| *m < 60).ok_or_else(|| invalid(1, "expected an integer below 60"))?;
+    let seconds = match tokens.get(2) {
+        Some(seconds) => seconds.parse::<f32>().ok().filter(|s| (0. ..60.).contains(s)).ok_or_else(|| invalid(2, "expected a number in 0..60"))?,
+        None => 0.
+    };
+    Ok((negative, whole as f32 + minutes as f32 / 60. + seconds / 3600.))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_simbad_coordinate_columns() {
+        let coord = parse_sexagesimal("06 45 08.917 -16 42 58.02").unwrap();
+        assert!((coord.right_ascension.to_degrees() - 101.287_15).abs() < 1e-4);
+        assert!((coord.declination.to_degrees() + 16.716_116).abs() < 1e-4);
+        let galactic = parse_decimal_degrees("227.23 -08.89").unwrap();
+        assert!((galactic.right_ascension.to_degrees() - 227.23).abs() < 1e-4);
+        assert!((galactic.declination.to_degrees() + 8.89).abs() < 1e-4);
+    }
+
+    // A short or empty last token used to index past the end of the string.
+    #[test]
+    fn truncated_coordinates_are_errors_not_panics() {
+        for input in ["", "06", "06 45 08.917 -", "06 45 08.917 - 42 58.02"] {
+            assert!(parse_sexagesimal(input).is_err(), "{:?}", input);
+        }
+        assert!(parse_decimal_degrees("227.23").is_err());
+        assert!(matches!(parse_sexagesimal("06 45 08.917"), Err(CoordParseError::MissingToken { .. })));
+        assert!(matches!(parse_sexagesimal("06 4x 08.917 -16 42 58.02"), Err(CoordParseError::InvalidToken { index: 1, .. })));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::Photometry;
+
+#[cfg_attr(feature = "schemars", derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Band {
+    U,
+    B,
+    V,
+    R,
+    I,
+    // Gaia G, as SIMBAD exports it.
+    G,
+    J,
+    H,
+    K
+}
+
+impl Band {
+    pub const ALL: [Band; 9] = [Band::U, Band::B, Band::V, Band::R, Band::I, Band::G, Band::J, Band::H, Band::K];
+}
+
+// Flux density of magnitude 0 per band, in Jansky.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ZeroPoints {
+    jansky: [f32; 9]
+}
+
+impl ZeroPoints {
+    // Johnson-Cousins-Glass from Bessell, Castelli & Plez (1998), Gaia G from the DR2 Vega system.
+    pub const VEGA: Self = Self { jansky: [1790., 4063., 3636., 3064., 2416., 3229., 1594., 1024., 666.7] };
+    pub const AB: Self = Self { jansky: [3631.; 9] };
+
+    pub fn get(&self, band: Band) -> f32 {
+        self.jansky[band as usize]
+    }
+
+    pub fn with(mut self, band: Band, jansky: f32) -> Self {
+        self.jansky[band as usize] = jansky;
+        self
+    }
+}
+
+impl Default for ZeroPoints {
+    fn default() -> Self {
+        Self::VEGA
+    }
+}
+
+impl Photometry {
+    pub fn get(&self, band: Band) -> Option<f32> {
+        self.magnitudes[band as usize]
+    }
+
+    pub fn set(&mut self, band: Band, magnitude: Option<f32>) {
+        self.magnitudes[band as usize] = magnitude;
+    }
+
+    pub fn with(mut self, band: Band, magnitude: Option<f32>) -> Self {
+        self.set(band, magnitude);
+        self
+    }
+
+    // Bands with a magnitude, in `Band::ALL` order.
+    pub fn iter(&self) -> impl Iterator<Item = (Band, f32)> + '_ {
+        Band::ALL.into_iter().filter_map(|band| self.get(band).map(|m| (band, m)))
+    }
+
+    // Applies `f` to every magnitude that is present.
+    pub fn map(&self, mut f: impl FnMut(Band, f32) -> f32) -> Photometry {
+        let mut mapped = *self;
+        for (band, m) in self.iter() {
+            mapped.set(band, Some(f(band, m)));
+        }
+        mapped
+    }
+
+    // Flux density in Jansky assuming SIMBAD's (Vega) magnitudes.
+    pub fn flux_jy(&self, band: Band) -> Option<f32> {
+        self.flux_jy_with(band, &ZeroPoints::VEGA)
+    }
+
+    pub fn flux_jy_with(&self, band: Band, zero_points: &ZeroPoints) -> Option<f32> {
+        self.get(band).map(|m| zero_points.get(band) * 10f32.powf(-0.4 * m))
+    }
+}
+
+// Colour used to move between photometric systems, in magnitudes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ColorIndex {
+    // Gaia G_BP - G_RP.
+    BpRp(f32),
+    // Johnson B - V.
+    BV(f32),
+    // Johnson V - Cousins I.
+    VI(f32)
+}
+
+fn polynomial(x: f32, coefficients: &[f32]) -> f32 {
+    coefficients.iter().rev().fold(0., |sum, c| sum * x + c)
+}
+
+// Magnitude in `band` below a reference magnitude (G for the Gaia relations, V otherwise), i.e.
+// m_band = m_ref - offset. None where `color` doesn't reach the band or lies outside the range
+// the relation was fitted on.
+fn offset(band: Band, color: ColorIndex) -> Option<f32> {
+    match color {
+        // Riello et al. (2021), Gaia EDR3 to Johnson-Cousins, for -0.5 < BP-RP < 2.75; and Evans et
+        // al. (2018), Gaia DR2 to 2MASS, for 0.25 < BP-RP < 5.5.
+        ColorIndex::BpRp(x) => match band {
+            Band::G => Some(0.),
+            Band::V | Band::R | Band::I if !(-0.5..2.75).contains(&x) => None,
+            Band::V => Some(polynomial(x, &[-0.02704, 0.01424, -0.2156, 0.01426])),
+            Band::R => Some(polynomial(x, &[-0.02275, 0.3961, -0.1243, -0.01396, 0.003775])),
+            Band::I => Some(polynomial(x, &[0.01753, 0.76, -0.0991])),
+            Band::J | Band::H | Band::K if !(0.25..5.5).contains(&x) => None,
+            Band::J => Some(polynomial(x, &[-0.01883, 1.394, -0.07893])),
+            Band::H => Some(polynomial(x, &[-0.1621, 1.968, -0.1328])),
+            Band::K => Some(polynomial(x, &[-0.1885, 2.092, -0.1345])),
+            Band::U | Band::B => None
+        },
+        ColorIndex::BV(x) => match band {
+            Band::V => Some(0.),
+            Band::B => Some(-x),
+            _ => None
+        },
+        // Riello et al. (2021), G - V against V - I, for -0.6 < V-I < 6.
+        ColorIndex::VI(x) => match band {
+            Band::V => Some(0.),
+            Band::I => Some(x),
+            Band::G if (-0.6..6.).contains(&x) => Some(polynomial(x, &[0.01597, 0.02809, 0.2483, -0.03656, 0.002939])),
+            _ => None
+        }
+    }
+}
+
+// What to add to a magnitude in `from` to get one in `to`, e.g. `convert(Band::G, Band::V,
+// ColorIndex::BpRp(0.8))` for Johnson V from Gaia G. None if `color` doesn't relate the two bands.
+pub fn convert(from: Band, to: Band, color: ColorIndex) -> Option<f32> {
+    if from == to {
+        return Some(0.);
+    }
+    Some(offset(from, color)? - offset(to, color)?)
+}
+
+impl Photometry {
+    // Fills every missing band `color` can reach from the first band (in `Band::ALL` order) that has
+    // a magnitude and a relation; measured magnitudes are kept.
+    pub fn fill_missing(&self, color: ColorIndex) -> Photometry {
+        let Some((source, m)) = self.iter().find(|(band, _)| offset(*band, color).is_some()) else { return *self };
+        let mut filled = *self;
+        for band in Band::ALL {
+            if self.get(band).is_none() {
+                filled.set(band, convert(source, band, color).map(|delta| m + delta));
+            }
+        }
+        filled
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::VecDeque;
+use std::io::{BufRead, Read};
+use crate::RecordSchema;
+
+// SIMBAD CSV exports can start with a query echo and separator lines and end with a blank line
+// and a row count. This passes on the header row and the lines that look like data rows, one
+// line at a time, so a CSV reader on top of it never needs the whole file in memory. The
+// delimiter is whichever of `DELIMITERS` splits the header: SIMBAD's own exports use ';', TAP's
+// CSV output ','.
+pub(crate) struct CleanReader<R> {
+    inner: R,
+    pub(crate) delimiter: char,
+    // Lines already taken from `inner` while looking for the header, the header first.
+    queued: VecDeque<Vec<u8>>,
+    line: Vec<u8>,
+    position: usize,
+    passed: u64,
+    pub(crate) preamble: usize,
+    // Counts only the lines read so far, so it is complete once the CSV reader hit the end.
+    pub(crate) trailing: usize,
+    // (first passed line, lines dropped before it) wherever the count changes, to map line
+    // numbers of the cleaned stream back to the input.
+    dropped: Vec<(u64, u64)>
+}
+
+impl<R: BufRead> CleanReader<R> {
+    pub(crate) fn new(mut inner: R) -> std::io::Result<Self> {
+        let mut lines = VecDeque::new();
+        let mut header = None;
+        let mut delimiter = DELIMITERS[0];
+        loop {
+            let mut line = vec![];
+            if inner.read_until(b'\n', &mut line)? == 0 {
+                break;
+            }
+            let found = header_delimiter(&String::from_utf8_lossy(&line));
+            lines.push_back(line);
+            if let Some(found) = found {
+                delimiter = found;
+                header = Some(lines.len() - 1);
+                break;
+            }
+        }
+        // Without a recognizable header (and only then is the whole input buffered) keep
+        // everything from the first delimited line and let the CSV reader report what's wrong.
+        let preamble = header
+            .or_else(|| lines.iter().position(|line| String::from_utf8_lossy(line).contains(delimiter)))
+            .unwrap_or(0);
+        lines.drain(..preamble);
+        Ok(Self {
+            inner,
+            delimiter,
+            queued: lines,
+            line: vec![],
+            position: 0,
+            passed: 0,
+            preamble,
+            trailing: 0,
+            dropped: vec![(1, preamble as u64)]
+        })
+    }
+
+    pub(crate) fn original_line(&self, line: u64) -> u64 {
+        let index = self.dropped.partition_point(|&(first, _)| first <= line);
+        line + index.checked_sub(1).map_or(0, |i| self.dropped[i].1)
+    }
+
+    // Loads the next line to pass on into `self.line`; false at the end of the input.
+    fn advance(&mut self) -> std::io::Result<bool> {
+        loop {
+            self.line.clear();
+            self.position = 0;
+            match self.queued.pop_front() {
+                Some(line) => self.line = line,
+                None if self.inner.read_until(b'\n', &mut self.line)? == 0 => return Ok(false),
+                None => {}
+            }
+            if self.passed > 0 && !is_data_row(&String::from_utf8_lossy(&self.line), self.delimiter) {
+                self.trailing += 1;
+                let dropped = self.preamble as u64 + self.trailing as u64;
+                match self.dropped.last_mut() {
+                    Some(last) if last.0 == self.passed + 1 => last.1 = dropped,
+                    _ => self.dropped.push((self.passed + 1, dropped))
+                }
+                continue;
+            }
+            self.passed += 1;
+            if !self.line.ends_with(b"\n") {
+                self.line.push(b'\n');
+            }
+            return Ok(true);
+        }
+    }
+}
+
+impl<R: BufRead> Read for CleanReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        if self.position == self.line.len() && !self.advance()? {
+            return Ok(0);
+        }
+        let rest = &self.line[self.position..];
+        let n = rest.len().min(buf.len());
+        buf[..n].copy_from_slice(&rest[..n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+pub(crate) const DELIMITERS: [char; 2] = [';', ','];
+
+// The delimiter that makes this line a header, if any.
+pub(crate) fn header_delimiter(line: &str) -> Option<char> {
+    DELIMITERS.into_iter().find(|&delimiter| is_header(line, delimiter))
+}
+
+pub(crate) fn is_header(line: &str, delimiter: char) -> bool {
+    line.split(delimiter).any(RecordSchema::is_identifier_column)
+}
+
+pub(crate) fn is_data_row(line: &str, delimiter: char) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty()
+        && trimmed.contains(delimiter)
+        && !trimmed.chars().all(|c| c == delimiter || "-=+ \t".contains(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{RecordBuilder, SimbadCsv};
+    use crate::{import_records_with_report, ImportWarning};
+
+    fn records() -> Vec<crate::Record> {
+        (1..=3).map(|id| RecordBuilder::new(id, &format!("* star {}", id)).coordinates("06 45 08.917 -16 42 58.02").build()).collect()
+    }
+
+    #[test]
+    fn skips_query_echo_separators_and_footer() {
+        let csv = SimbadCsv::new(records())
+            .preamble(&["C.D.S.  -  SIMBAD4 rel 1.8", "", "simbad query: Vmag < 1", "--------------------"])
+            .footer(true);
+        let text = csv.render().replacen("\n2;", "\n;;;;\n\n2;", 1);
+        let path = std::env::temp_dir().join(format!("simbad-{}-preamble.csv", std::process::id()));
+        std::fs::write(&path, text).unwrap();
+        let report = import_records_with_report(&path).unwrap();
+        assert_eq!(report.records.iter().map(|r| (r.id, r.line)).collect::<Vec<_>>(), [(1, 6), (2, 9), (3, 10)]);
+        assert_eq!(report.warnings, [ImportWarning::IgnoredLines { preamble: 4, trailing: 4 }]);
+    }
+
+    #[test]
+    fn plain_export_has_no_ignored_lines() {
+        let path = SimbadCsv::new(records()).write_temp("plain.csv").unwrap();
+        let report = import_records_with_report(&path).unwrap();
+        assert_eq!(report.records.len(), 3);
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn reports_rows_after_a_missing_header_instead_of_dropping_them() {
+        let path = std::env::temp_dir().join(format!("simbad-{}-headerless.csv", std::process::id()));
+        std::fs::write(&path, "query\n1;2;3\n4;5;6\n").unwrap();
+        assert!(import_records_with_report(&path).map_or(true, |report| report.records.is_empty() && !report.skipped.is_empty()));
+    }
+}
@@ -0,0 +1,66 @@
+use std::f32::consts::{FRAC_PI_2, PI, SQRT_2};
+use glam::Vec2;
+use crate::{Angle, EquatorialCoordinate};
+
+// Longitudes for the all-sky projections are measured from RA 0h, wrapped to -π..π.
+fn signed_ra(coord: &EquatorialCoordinate) -> f32 {
+    coord.right_ascension.wrap_signed()
+}
+
+// Tangent-plane (standard) coordinates in radians. None for points 90° or more from `center`.
+pub fn project_gnomonic(coord: &EquatorialCoordinate, center: &EquatorialCoordinate) -> Option<Vec2> {
+    let (sin_d, cos_d) = coord.declination.sin_cos();
+    let (sin_d0, cos_d0) = center.declination.sin_cos();
+    let (sin_da, cos_da) = (coord.right_ascension - center.right_ascension).sin_cos();
+    let cos_c = sin_d0 * sin_d + cos_d0 * cos_d * cos_da;
+    if cos_c <= 0. { return None; }
+    Some(Vec2::new(cos_d * sin_da / cos_c, (cos_d0 * sin_d - sin_d0 * cos_d * cos_da) / cos_c))
+}
+
+pub fn unproject_gnomonic(point: Vec2, center: &EquatorialCoordinate) -> EquatorialCoordinate {
+    let rho = point.length();
+    if rho == 0. { return *center; }
+    let (sin_c, cos_c) = rho.atan().sin_cos();
+    let (sin_d0, cos_d0) = center.declination.sin_cos();
+    let dec = (cos_c * sin_d0 + point.y * sin_c * cos_d0 / rho).clamp(-1., 1.).asin();
+    let ra = center.right_ascension + (point.x * sin_c).atan2(rho * cos_d0 * cos_c - point.y * sin_d0 * sin_c);
+    EquatorialCoordinate::new(ra, dec)
+}
+
+// Equal-area, x in -2√2..2√2 and y in -√2..√2.
+pub fn project_mollweide(coord: &EquatorialCoordinate) -> Vec2 {
+    let target = PI * coord.declination.sin();
+    let mut theta = coord.declination;
+    if coord.declination.abs() < FRAC_PI_2 {
+        for _ in 0..50 {
+            let delta = (2. * theta + (2. * theta).sin() - target) / (2. + 2. * (2. * theta).cos());
+            theta -= delta;
+            if delta.abs() < 1e-7 { break; }
+        }
+    }
+    Vec2::new(2. * SQRT_2 / PI * signed_ra(coord) * theta.cos(), SQRT_2 * theta.sin())
+}
+
+pub fn unproject_mollweide(point: Vec2) -> Option<EquatorialCoordinate> {
+    if (point.x / (2. * SQRT_2)).powi(2) + (point.y / SQRT_2).powi(2) > 1. { return None; }
+    let theta = (point.y / SQRT_2).clamp(-1., 1.).asin();
+    let dec = ((2. * theta + (2. * theta).sin()) / PI).clamp(-1., 1.).asin();
+    let ra = if theta.cos() == 0. { 0. } else { PI * point.x / (2. * SQRT_2 * theta.cos()) };
+    Some(EquatorialCoordinate::new(ra, dec))
+}
+
+// Equal-area, same extent as Mollweide.
+pub fn project_hammer_aitoff(coord: &EquatorialCoordinate) -> Vec2 {
+    let lon = signed_ra(coord);
+    let (sin_d, cos_d) = coord.declination.sin_cos();
+    let z = (1. + cos_d * (lon / 2.).cos()).sqrt();
+    Vec2::new(2. * SQRT_2 * cos_d * (lon / 2.).sin() / z, SQRT_2 * sin_d / z)
+}
+
+pub fn unproject_hammer_aitoff(point: Vec2) -> Option<EquatorialCoordinate> {
+    if (point.x / (2. * SQRT_2)).powi(2) + (point.y / SQRT_2).powi(2) > 1. { return None; }
+    let z = (1. - (point.x / 4.).powi(2) - (point.y / 2.).powi(2)).sqrt();
+    let ra = 2. * (z * point.x).atan2(2. * (2. * z * z - 1.));
+    let dec = (z * point.y).clamp(-1., 1.).asin();
+    Some(EquatorialCoordinate::new(ra, dec))
+}
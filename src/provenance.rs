@@ -0,0 +1,47 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+#[cfg_attr(feature = "schemars", derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Source {
+    File(PathBuf),
+    // Query text, e.g. an ADQL statement.
+    Query(String),
+    // Named built-in or external catalog, e.g. "bright_stars".
+    Catalog(String)
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Query(query) => write!(f, "query \"{}\"", query),
+            Self::Catalog(name) => write!(f, "catalog {}", name)
+        }
+    }
+}
+
+// Where a star came from. Stars built from several rows (merged systems, dropped duplicates)
+// carry one entry per row.
+#[cfg_attr(feature = "schemars", derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Provenance {
+    pub source: Source,
+    // Line in the source file, or row index for catalogs.
+    pub row: Option<u64>
+}
+
+impl Provenance {
+    pub fn new(source: Source, row: Option<u64>) -> Self {
+        Self { source, row }
+    }
+}
+
+impl Display for Provenance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.row {
+            Some(row) => write!(f, "{}:{}", self.source, row),
+            None => write!(f, "{}", self.source)
+        }
+    }
+}
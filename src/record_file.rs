@@ -0,0 +1,119 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::ops::Range;
+use std::path::Path;
+use csv::{ReaderBuilder, StringRecord};
+use crate::{preamble, Record, SimbadError};
+use crate::schema::ResolvedHeader;
+
+// Random access to the rows of a SIMBAD export without parsing all of it. `open` makes one pass
+// to remember where every data row starts, `get_row` and `rows` then seek straight to it. Rows
+// are assumed to be single lines, which holds for SIMBAD's ';' separated exports and TAP's CSV.
+#[derive(Debug)]
+pub struct RecordFile {
+    reader: BufReader<File>,
+    header: StringRecord,
+    resolved: ResolvedHeader,
+    delimiter: char,
+    // (byte offset, 1-based line in the file) of every data row.
+    rows: Vec<(u64, u64)>
+}
+
+impl RecordFile {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut header = None;
+        let mut delimiter = preamble::DELIMITERS[0];
+        let mut rows = vec![];
+        let mut line = String::new();
+        let (mut offset, mut number) = (0u64, 0u64);
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 {
+                break;
+            }
+            number += 1;
+            if header.is_none() {
+                if let Some(found) = preamble::header_delimiter(&line) {
+                    delimiter = found;
+                    header = Some(parse_line(&line, delimiter)?);
+                }
+            } else if preamble::is_data_row(&line, delimiter) {
+                rows.push((offset, number));
+            }
+            offset += read as u64;
+        }
+        let header = header.ok_or(SimbadError::InvalidFormat)?;
+        let resolved = ResolvedHeader::new(&header);
+        Ok(Self { reader, header, resolved, delimiter, rows })
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn header(&self) -> &StringRecord {
+        &self.header
+    }
+
+    // The `n`th data row, counting from 0 after the header.
+    pub fn get_row(&mut self, n: usize) -> Result<Record, Box<dyn std::error::Error>> {
+        let end = n.checked_add(1).ok_or(SimbadError::RowOutOfRange(n))?;
+        let mut records = self.rows(n..end)?;
+        Ok(records.remove(0))
+    }
+
+    // Seeks once and reads forward, skipping any non-data lines between indexed rows.
+    pub fn rows(&mut self, range: Range<usize>) -> Result<Vec<Record>, Box<dyn std::error::Error>> {
+        if range.end > self.rows.len() {
+            return Err(SimbadError::RowOutOfRange(range.end.saturating_sub(1)).into());
+        }
+        let Some(&(start, _)) = self.rows.get(range.start).filter(|_| !range.is_empty()) else {
+            return Ok(vec![]);
+        };
+        self.reader.seek(SeekFrom::Start(start))?;
+        let mut records = Vec::with_capacity(range.len());
+        let mut position = start;
+        let mut line = String::new();
+        for &(offset, number) in &self.rows[range] {
+            while position <= offset {
+                line.clear();
+                let read = self.reader.read_line(&mut line)?;
+                if read == 0 {
+                    return Err(SimbadError::InvalidFormat.into());
+                }
+                position += read as u64;
+            }
+            let mut record = self.resolved.record(&parse_line(&line, self.delimiter)?)?;
+            record.line = number;
+            records.push(record);
+        }
+        Ok(records)
+    }
+}
+
+fn parse_line(line: &str, delimiter: char) -> Result<StringRecord, csv::Error> {
+    let mut record = StringRecord::new();
+    ReaderBuilder::new().delimiter(delimiter as u8).has_headers(false).from_reader(line.as_bytes()).read_record(&mut record)?;
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_past_the_end_are_errors() {
+        let mut file = RecordFile::open(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/preamble.csv")).unwrap();
+        assert_eq!(file.len(), 3);
+        assert_eq!(file.get_row(1).unwrap().identifier(), "* alf Lyr");
+        assert_eq!(file.get_row(1).unwrap().line(), 9);
+        assert!(file.get_row(3).is_err());
+        assert!(file.get_row(usize::MAX).is_err());
+    }
+}
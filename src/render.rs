@@ -0,0 +1,158 @@
+use crate::{Band, SpectralType, Star};
+
+// Maps apparent magnitude to on-screen size and glow. Sizes are in pixels, or whatever unit the
+// caller draws points in.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RenderOptions {
+    // Faintest magnitude drawn; it gets `min_size`.
+    pub(crate) limiting_magnitude: f32,
+    pub(crate) min_size: f32,
+    pub(crate) max_size: f32,
+    // Size ratio per magnitude, e.g. 1.25 makes a star one magnitude brighter 25 % larger.
+    pub(crate) size_step: f32,
+    // Stars brighter than this get a halo, reaching full intensity at `max_size`.
+    pub(crate) halo_magnitude: f32,
+    // Scintillation amplitude at the zenith, 0 to 1.
+    pub(crate) twinkle: f32
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self { limiting_magnitude: 6.5, min_size: 1., max_size: 8., size_step: 1.25, halo_magnitude: 1.5, twinkle: 0.05 }
+    }
+}
+
+impl RenderOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn limiting_magnitude(mut self, magnitude: f32) -> Self {
+        self.limiting_magnitude = magnitude;
+        self
+    }
+
+    pub fn size_range(mut self, min: f32, max: f32) -> Self {
+        self.min_size = min;
+        self.max_size = max;
+        self
+    }
+
+    pub fn size_step(mut self, step: f32) -> Self {
+        self.size_step = step;
+        self
+    }
+
+    pub fn halo_magnitude(mut self, magnitude: f32) -> Self {
+        self.halo_magnitude = magnitude;
+        self
+    }
+
+    pub fn twinkle(mut self, amplitude: f32) -> Self {
+        self.twinkle = amplitude;
+        self
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Appearance {
+    pub size: f32,
+    // sRGB, each channel 0 to 1, brightest channel at 1.
+    pub color: [f32; 3],
+    // 0 to 1.
+    pub halo: f32
+}
+
+// 0 for stars fainter than the limiting magnitude, so callers can skip them.
+pub fn point_size(magnitude: f32, options: &RenderOptions) -> f32 {
+    if magnitude > options.limiting_magnitude {
+        return 0.;
+    }
+    (options.min_size * options.size_step.powf(options.limiting_magnitude - magnitude)).min(options.max_size)
+}
+
+pub fn halo_intensity(magnitude: f32, options: &RenderOptions) -> f32 {
+    // Magnitude at which `point_size` reaches `max_size`.
+    let saturation = options.limiting_magnitude - (options.max_size / options.min_size).ln() / options.size_step.ln();
+    if magnitude >= options.halo_magnitude {
+        return 0.;
+    }
+    if saturation >= options.halo_magnitude {
+        return 1.;
+    }
+    ((options.halo_magnitude - magnitude) / (options.halo_magnitude - saturation)).clamp(0., 1.)
+}
+
+// Scintillation grows with airmass roughly as X^1.75 (Dravins et al. 1998); altitude in radians.
+// Objects below the horizon don't twinkle.
+pub fn twinkle_amplitude(altitude: f32, options: &RenderOptions) -> f32 {
+    if altitude <= 0. {
+        return 0.;
+    }
+    let airmass = 1. / altitude.sin().max(0.05);
+    (options.twinkle * airmass.powf(1.75)).min(1.)
+}
+
+// Main-sequence effective temperatures (K), keyed by sequence index.
+const MAIN_SEQUENCE_TEFF: [(f32, f32); 14] = [
+    (5., 42000.),
+    (10., 30000.),
+    (15., 15200.),
+    (20., 9790.),
+    (25., 8180.),
+    (30., 7300.),
+    (35., 6650.),
+    (40., 5940.),
+    (45., 5560.),
+    (50., 5150.),
+    (55., 4410.),
+    (60., 3840.),
+    (65., 3170.),
+    (68., 2650.)
+];
+
+pub fn effective_temperature(spec_type: &SpectralType) -> f32 {
+    let index = spec_type.sequence_index().clamp(MAIN_SEQUENCE_TEFF[0].0, MAIN_SEQUENCE_TEFF[MAIN_SEQUENCE_TEFF.len() - 1].0);
+    MAIN_SEQUENCE_TEFF.windows(2).find(|w| index >= w[0].0 && index <= w[1].0).map_or(MAIN_SEQUENCE_TEFF[0].1, |w| {
+        let t = (index - w[0].0) / (w[1].0 - w[0].0);
+        w[0].1 + (w[1].1 - w[0].1) * t
+    })
+}
+
+// Ballesteros (2012).
+pub fn temperature_from_color_index(b_minus_v: f32) -> f32 {
+    4600. * (1. / (0.92 * b_minus_v + 1.7) + 1. / (0.92 * b_minus_v + 0.62))
+}
+
+// Blackbody colour, after Tanner Helland's fit; good from 1000 K to 40000 K.
+pub fn temperature_color(kelvin: f32) -> [f32; 3] {
+    let t = kelvin.clamp(1000., 40000.) / 100.;
+    let red = if t <= 66. { 255. } else { 329.699 * (t - 60.).powf(-0.133_204_76) };
+    let green = if t <= 66. { 99.470_8 * t.ln() - 161.119_57 } else { 288.122_17 * (t - 60.).powf(-0.075_514_85) };
+    let blue = if t >= 66. { 255. } else if t <= 19. { 0. } else { 138.517_73 * (t - 10.).ln() - 305.044_8 };
+    let rgb = [red, green, blue].map(|c| c.clamp(0., 255.) / 255.);
+    let max = rgb.iter().copied().fold(0f32, f32::max);
+    rgb.map(|c| c / max)
+}
+
+// Size and halo follow `magnitude`; the colour comes from the spectral type, or white without one.
+pub fn appearance(spec_type: Option<&SpectralType>, magnitude: f32, options: &RenderOptions) -> Appearance {
+    Appearance {
+        size: point_size(magnitude, options),
+        color: spec_type.map_or([1.; 3], |s| temperature_color(effective_temperature(s))),
+        halo: halo_intensity(magnitude, options)
+    }
+}
+
+impl Star {
+    // From V and the spectral type, falling back to B-V for the colour. None without V.
+    pub fn appearance(&self, options: &RenderOptions) -> Option<Appearance> {
+        let v = self.photometry.get(Band::V)?;
+        let spec_type = self.spectral_type();
+        let mut appearance = appearance(spec_type.as_ref(), v, options);
+        if let (None, Some(b)) = (spec_type, self.photometry.get(Band::B)) {
+            appearance.color = temperature_color(temperature_from_color_index(b - v));
+        }
+        Some(appearance)
+    }
+}
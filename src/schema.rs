@@ -0,0 +1,271 @@
+use std::borrow::Cow;
+use csv::StringRecord;
+use crate::{ColumnSet, ParallaxUnit, Record, SlimRecord};
+
+// Column names SIMBAD has used for each `Record` field. Header cells are matched after trimming
+// and ignoring ASCII case, and the field name itself is always accepted, so a new spelling only
+// needs a new schema (or alias) here rather than another serde attribute.
+#[derive(Debug, PartialEq)]
+pub struct RecordSchema {
+    pub version: u16,
+    pub name: &'static str,
+    columns: &'static [(&'static str, &'static [&'static str])]
+}
+
+impl RecordSchema {
+    // The ';' separated export of the SIMBAD web interface.
+    pub const ASCII: RecordSchema = RecordSchema {
+        version: 1,
+        name: "ascii",
+        columns: &[
+            ("id", &["#"]),
+            ("ids", &["all ids"]),
+            ("coord1", &["coord1 (ICRS,J2000/2000)"]),
+            ("coord2", &["coord2 (FK5,J2000/2000)"]),
+            ("coord3", &["coord3 (FK4,B1950/1950)"]),
+            ("coord4", &["coord4 (Gal,J2000/2000)"]),
+            ("mag_u", &["Mag U"]),
+            ("mag_b", &["Mag B"]),
+            ("mag_v", &["Mag V"]),
+            ("mag_r", &["Mag R"]),
+            ("mag_i", &["Mag I"]),
+            ("mag_g", &["Mag G"]),
+            ("mag_j", &["Mag J"]),
+            ("mag_h", &["Mag H"]),
+            ("mag_k", &["Mag K"]),
+            ("spec_type", &["spec. type"]),
+            ("morph_type", &["morph. type"]),
+            ("ang_size", &["ang. size"]),
+            ("pretty_name", &["pretty name"]),
+            ("var_type", &["var. type"]),
+            ("var_period", &["var. period"]),
+            ("var_amplitude", &["var. amplitude"])
+        ]
+    };
+
+    // Column names of the TAP `basic`, `ids` and `allfluxes` tables, as returned by ADQL queries
+    // that don't rename their columns. `ra` and `dec` (ICRS, decimal degrees) have no `Record` field
+    // of their own; `ResolvedHeader` joins them into coord1.
+    pub const TAP: RecordSchema = RecordSchema {
+        version: 2,
+        name: "tap",
+        columns: &[
+            ("id", &["oid"]),
+            ("identifier", &["main_id"]),
+            ("typ", &["otype"]),
+            ("ra", &["ra"]),
+            ("dec", &["dec"]),
+            ("plx", &["plx_value"]),
+            ("radvel", &["rvz_radvel"]),
+            ("redshift", &["rvz_redshift"]),
+            ("mag_u", &["U"]),
+            ("mag_b", &["B"]),
+            ("mag_v", &["V"]),
+            ("mag_r", &["R"]),
+            ("mag_i", &["I"]),
+            ("mag_g", &["G"]),
+            ("mag_j", &["J"]),
+            ("mag_h", &["H"]),
+            ("mag_k", &["K"]),
+            ("spec_type", &["sp_type"]),
+            ("morph_type", &["morph_type"]),
+            ("ang_size", &["galdim_majaxis"])
+        ]
+    };
+
+    // Oldest first.
+    pub const ALL: [&'static RecordSchema; 2] = [&Self::ASCII, &Self::TAP];
+
+    // `Record` field for a header cell, if the schema knows it. A trailing unit annotation such as
+    // "plx (arcsec)" or "Mag V [mag]" is ignored when the cell doesn't match as a whole.
+    pub fn resolve(&self, column: &str) -> Option<&'static str> {
+        let column = column.trim();
+        self.resolve_exact(column).or_else(|| {
+            let (name, _) = column.strip_suffix([')', ']'])?.rsplit_once(['(', '['])?;
+            self.resolve_exact(name.trim())
+        })
+    }
+
+    fn resolve_exact(&self, column: &str) -> Option<&'static str> {
+        FIELDS.iter().copied().find(|field| field.eq_ignore_ascii_case(column))
+            .or_else(|| self.columns.iter()
+                .find(|(_, aliases)| aliases.iter().any(|alias| alias.eq_ignore_ascii_case(column)))
+                .map(|(field, _)| *field))
+    }
+
+    // Header cell this schema writes for a `Record` field: its first alias, else the field name.
+    pub fn column(&self, field: &str) -> Option<&'static str> {
+        let field = FIELDS.iter().copied().find(|f| *f == field)?;
+        Some(self.columns.iter().find(|(f, _)| *f == field).and_then(|(_, aliases)| aliases.first().copied()).unwrap_or(field))
+    }
+
+    // The schema that recognizes most of the header; the newest one on a tie.
+    pub fn detect<'a>(header: impl IntoIterator<Item = &'a str> + Clone) -> &'static RecordSchema {
+        Self::ALL.into_iter()
+            .max_by_key(|schema| header.clone().into_iter().filter(|column| schema.resolve(column).is_some()).count())
+            .unwrap_or(&Self::ASCII)
+    }
+
+    // Whether some version of the schema reads this header cell as the identifier column.
+    pub(crate) fn is_identifier_column(column: &str) -> bool {
+        Self::ALL.iter().any(|schema| schema.resolve(column) == Some("identifier"))
+    }
+}
+
+// Deserializable `Record` fields, in declaration order (which is also the serialization order).
+pub(crate) const FIELDS: &[&str] = &[
+    "id", "identifier", "typ", "ids", "coord1", "coord2", "coord3", "coord4", "coo_err_maj", "coo_err_min",
+    "coo_err_angle", "pm", "plx", "radvel", "redshift", "cz", "mag_u", "mag_b", "mag_v", "mag_r", "mag_i",
+    "mag_g", "mag_j", "mag_h", "mag_k", "spec_type", "morph_type", "ang_size", "pretty_name", "var_type",
+    "var_period", "var_amplitude"
+];
+
+// Fields holding numbers or whitespace-separated lists of them (coordinates, proper motion).
+pub(crate) const NUMERIC_FIELDS: &[&str] = &[
+    "id", "ra", "dec", "coord1", "coord2", "coord3", "coord4", "coo_err_maj", "coo_err_min", "coo_err_angle", "pm", "plx",
+    "radvel", "redshift", "cz", "mag_u", "mag_b", "mag_v", "mag_r", "mag_i", "mag_g", "mag_j", "mag_h", "mag_k",
+    "var_period", "var_amplitude"
+];
+
+// A header rewritten to `Record` field names, remembering the columns no schema knows.
+#[derive(Debug)]
+pub(crate) struct ResolvedHeader {
+    fields: StringRecord,
+    unknown: Vec<(usize, String)>,
+    // Unit named by the parallax column, see `ParallaxUnit::from_column`.
+    parallax_unit: Option<ParallaxUnit>,
+    // Columns of separate ra and dec values to join into coord1, for headers without one.
+    split_coordinates: Option<(usize, usize)>
+}
+
+impl ResolvedHeader {
+    pub(crate) fn new(header: &StringRecord) -> Self {
+        let schema = RecordSchema::detect(header.iter());
+        let mut fields = StringRecord::new();
+        let mut unknown = vec![];
+        let mut parallax_unit = None;
+        for (index, column) in header.iter().enumerate() {
+            match schema.resolve(column) {
+                Some(field) => {
+                    if field == "plx" {
+                        parallax_unit = parallax_unit.or(ParallaxUnit::from_column(column));
+                    }
+                    fields.push_field(field);
+                }
+                None => {
+                    fields.push_field(column);
+                    unknown.push((index, column.trim().to_string()));
+                }
+            }
+        }
+        let position = |name: &str| fields.iter().position(|field| field == name);
+        let split_coordinates = match (position("coord1"), position("ra"), position("dec")) {
+            (None, Some(ra), Some(dec)) => Some((ra, dec)),
+            _ => None
+        };
+        if split_coordinates.is_some() {
+            fields.push_field("coord1");
+        }
+        Self { fields, unknown, parallax_unit, split_coordinates }
+    }
+
+    // The row with the joined coord1 appended when the header has separate ra and dec columns.
+    fn joined<'a>(&self, row: &'a StringRecord) -> Cow<'a, StringRecord> {
+        let Some((ra, dec)) = self.split_coordinates else { return Cow::Borrowed(row) };
+        let mut joined = row.clone();
+        match (row.get(ra).map(str::trim), row.get(dec).map(str::trim)) {
+            (Some(ra), Some(dec)) if !ra.is_empty() && !dec.is_empty() => joined.push_field(&format!("{} {}", ra, dec)),
+            _ => joined.push_field("")
+        }
+        Cow::Owned(joined)
+    }
+
+    pub(crate) fn parallax_unit(&self) -> Option<ParallaxUnit> {
+        self.parallax_unit
+    }
+
+    pub(crate) fn is_numeric(&self, index: usize) -> bool {
+        self.fields.get(index).is_some_and(|field| NUMERIC_FIELDS.contains(&field))
+    }
+
+    pub(crate) fn unknown_columns(&self) -> Vec<String> {
+        self.unknown.iter().map(|(_, column)| column.clone()).collect()
+    }
+
+    pub(crate) fn record_with(&self, row: &StringRecord, columns: ColumnSet) -> Result<Record, csv::Error> {
+        match columns {
+            ColumnSet::All => self.record(row),
+            ColumnSet::Minimal => self.joined(row).deserialize::<SlimRecord>(Some(&self.fields)).map(Record::from)
+        }
+    }
+
+    pub(crate) fn record(&self, row: &StringRecord) -> Result<Record, csv::Error> {
+        let mut record: Record = self.joined(row).deserialize(Some(&self.fields))?;
+        record.extra = self.unknown.iter()
+            .filter_map(|(index, column)| row.get(*index).map(|value| (column.clone(), value.to_string())))
+            .collect();
+        Ok(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{import_records_with_report, ImportWarning, Record, StellarPosition};
+
+    fn sample(name: &str) -> String {
+        format!("{}/tests/data/{}", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    // id, identifier, plx, V and spectral type.
+    type Summary<'a> = (usize, &'a str, Option<f32>, Option<f32>, Option<&'a str>);
+
+    fn summary(records: &[Record]) -> Vec<Summary<'_>> {
+        records.iter().map(|r| (r.id, r.identifier.as_str(), r.plx, r.mag_v, r.spec_type.as_deref())).collect()
+    }
+
+    const EXPECTED: [Summary<'static>; 3] = [
+        (1, "* alf CMa", Some(379.21), Some(-1.46), Some("A1V")),
+        (2, "* alf Lyr", Some(130.23), Some(0.03), Some("A0Va")),
+        (3, "* alf Cen A", Some(742.12), Some(0.01), Some("G2V"))
+    ];
+
+    #[test]
+    fn imports_ascii_export() {
+        let report = import_records_with_report(sample("ascii.csv")).unwrap();
+        assert_eq!(summary(&report.records), EXPECTED);
+        assert!(report.skipped.is_empty());
+        assert!(report.warnings.is_empty());
+        assert_eq!(report.records[0].coord4.as_deref(), Some("227.23 -08.89"));
+        assert_eq!(report.records[2].pretty_name.as_deref(), Some("Rigil Kentaurus"));
+    }
+
+    #[test]
+    fn imports_export_with_preamble_and_footer() {
+        let report = import_records_with_report(sample("preamble.csv")).unwrap();
+        assert_eq!(summary(&report.records), EXPECTED);
+        assert_eq!(report.records.iter().map(|r| r.line).collect::<Vec<_>>(), [8, 9, 10]);
+        assert_eq!(report.warnings, [ImportWarning::IgnoredLines { preamble: 5, trailing: 3 }]);
+    }
+
+    #[test]
+    fn imports_tap_export() {
+        let report = import_records_with_report(sample("tap.csv")).unwrap();
+        assert_eq!(summary(&report.records), EXPECTED);
+        assert!(report.skipped.is_empty());
+        assert!(report.warnings.is_empty());
+        assert_eq!(report.records[1].coord1.as_deref(), Some("279.23473479 38.78368896"));
+        assert!(report.records[1].extra.is_empty());
+    }
+
+    #[test]
+    fn tap_ra_and_dec_place_the_stars() {
+        let stars = crate::import(sample("tap.csv")).unwrap();
+        let expected: [(f64, f64); 3] = [(101.28715533, -16.71611586), (279.23473479, 38.78368896), (219.90085, -60.835619)];
+        assert_eq!(stars.len(), expected.len());
+        for (star, (ra, dec)) in stars.iter().zip(expected) {
+            let coord = StellarPosition::from(star.pos).coord;
+            assert!((coord.right_ascension.to_degrees() as f64 - ra).abs() < 1e-4, "{} ra {}", star.identifier, coord.right_ascension.to_degrees());
+            assert!((coord.declination.to_degrees() as f64 - dec).abs() < 1e-4, "{} dec {}", star.identifier, coord.declination.to_degrees());
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use glam::{DVec3, Vec3A};
+use crate::EquatorialCoordinate;
+
+fn unit_vector_f64(coord: &EquatorialCoordinate) -> DVec3 {
+    let (sin_ra, cos_ra) = (coord.right_ascension as f64).sin_cos();
+    let (sin_dec, cos_dec) = (coord.declination as f64).sin_cos();
+    DVec3::new(cos_dec * cos_ra, cos_dec * sin_ra, sin_dec)
+}
+
+// atan2(|a×b|, a·b) keeps precision for both tiny and near-antipodal separations, and has no
+// trouble at the poles.
+pub(crate) fn angle_between(a: Vec3A, b: Vec3A) -> f32 {
+    a.cross(b).length().atan2(a.dot(b))
+}
+
+// Angular separations (radians) from `center` to each of `coords`, in f64 so arcsecond-scale
+// separations keep their digits. A plain loop: the sines and cosines per coordinate dominate and
+// glam only vectorizes f32, so there is no SIMD path.
+pub fn separations_batch(center: &EquatorialCoordinate, coords: &[EquatorialCoordinate]) -> Vec<f64> {
+    let c = unit_vector_f64(center);
+    coords.iter().map(|coord| {
+        let v = unit_vector_f64(coord);
+        c.cross(v).length().atan2(c.dot(v))
+    }).collect()
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::{Band, Catalog, EquatorialCoordinate, Star, StarKey, StellarPosition};
+
+// Immutable catalog with name, position and magnitude indexes built once up front. Cloning only
+// bumps a reference count and every query takes `&self`, so clones can be handed to as many
+// threads as needed without locking.
+#[derive(Clone, Debug)]
+pub struct SharedCatalog {
+    inner: Arc<Indexed>
+}
+
+#[derive(Debug)]
+struct Indexed {
+    catalog: Catalog,
+    // Identifier, proper name and aliases of every star.
+    names: HashMap<StarKey, Vec<usize>>,
+    // Star indices and their coordinates, ordered by declination.
+    by_declination: Vec<(f32, usize, EquatorialCoordinate)>,
+    // Stars with a V magnitude, brightest first.
+    by_magnitude: Vec<(f32, usize)>
+}
+
+impl SharedCatalog {
+    pub fn new(catalog: Catalog) -> Self {
+        let mut names: HashMap<StarKey, Vec<usize>> = HashMap::new();
+        let mut by_declination = Vec::with_capacity(catalog.len());
+        let mut by_magnitude = vec![];
+        for (index, star) in catalog.stars.iter().enumerate() {
+            // An empty key would file every unnamed star under the same name.
+            let mut keys = [star.identifier.as_str(), star.name.as_str()].into_iter().chain(star.aliases.iter().map(|a| a.as_str()))
+                .map(StarKey::from_identifier)
+                .filter(|key| !key.as_str().is_empty())
+                .collect::<Vec<_>>();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                names.entry(key).or_default().push(index);
+            }
+            let coord = StellarPosition::from(star.pos).coord;
+            by_declination.push((coord.declination, index, coord));
+            if let Some(v) = star.photometry.get(Band::V).filter(|v| v.is_finite()) {
+                by_magnitude.push((v, index));
+            }
+        }
+        by_declination.sort_by(|a, b| a.0.total_cmp(&b.0));
+        by_magnitude.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { inner: Arc::new(Indexed { catalog, names, by_declination, by_magnitude }) }
+    }
+
+    pub fn catalog(&self) -> &Catalog {
+        &self.inner.catalog
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.catalog.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.catalog.is_empty()
+    }
+
+    // Case- and whitespace-insensitive exact match on identifier, proper name or alias.
+    pub fn find_by_name(&self, name: &str) -> Vec<&Star> {
+        self.inner.names.get(&StarKey::from_identifier(name))
+            .map(|indices| indices.iter().map(|&i| &self.inner.catalog.stars[i]).collect())
+            .unwrap_or_default()
+    }
+
+    // Stars within `radius` (radians) of `center`, in declination order.
+    pub fn within(&self, center: &EquatorialCoordinate, radius: f32) -> Vec<&Star> {
+        let stars = &self.inner.by_declination;
+        let start = stars.partition_point(|(dec, _, _)| *dec < center.declination - radius);
+        let end = stars.partition_point(|(dec, _, _)| *dec <= center.declination + radius);
+        stars[start..end].iter()
+            .filter(|(_, _, coord)| center.separation(coord) <= radius)
+            .map(|&(_, i, _)| &self.inner.catalog.stars[i])
+            .collect()
+    }
+
+    // Stars with V at or below `magnitude`, brightest first.
+    pub fn brighter_than(&self, magnitude: f32) -> impl Iterator<Item = &Star> + '_ {
+        let end = self.inner.by_magnitude.partition_point(|(v, _)| *v <= magnitude);
+        self.inner.by_magnitude[..end].iter().map(|&(_, i)| &self.inner.catalog.stars[i])
+    }
+
+    // The `n` brightest stars in V.
+    pub fn brightest(&self, n: usize) -> impl Iterator<Item = &Star> + '_ {
+        self.inner.by_magnitude.iter().take(n).map(|&(_, i)| &self.inner.catalog.stars[i])
+    }
+}
+
+impl From<Catalog> for SharedCatalog {
+    fn from(catalog: Catalog) -> Self {
+        Self::new(catalog)
+    }
+}
@@ -0,0 +1,135 @@
+use serde::Deserialize;
+use crate::{de, parse, Record};
+
+// Deserialization target for `ColumnSet::Minimal`: borrows from the CSV row and leaves out cz,
+// morphology and angular size, which star import never needs. Converting into a `Record` only
+// allocates for the columns that are kept, and for the FK5, FK4 and galactic coordinates only
+// when coord1 doesn't hold a position, since import then falls back to them.
+#[derive(Deserialize, Debug)]
+pub struct SlimRecord<'a> {
+    id: usize,
+    identifier: &'a str,
+    typ: &'a str,
+    #[serde(default, borrow, deserialize_with = "de::borrowed_null_marker")]
+    ids: Option<&'a str>,
+    #[serde(default, borrow, deserialize_with = "de::borrowed_null_marker")]
+    coord1: Option<&'a str>,
+    #[serde(default, borrow, deserialize_with = "de::borrowed_null_marker")]
+    coord2: Option<&'a str>,
+    #[serde(default, borrow, deserialize_with = "de::borrowed_null_marker")]
+    coord3: Option<&'a str>,
+    #[serde(default, borrow, deserialize_with = "de::borrowed_null_marker")]
+    coord4: Option<&'a str>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    coo_err_maj: Option<f32>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    coo_err_min: Option<f32>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    coo_err_angle: Option<f32>,
+    #[serde(default, borrow, deserialize_with = "de::borrowed_null_marker")]
+    pm: Option<&'a str>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    plx: Option<f32>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    radvel: Option<f32>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    redshift: Option<f32>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    mag_u: Option<f32>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    mag_b: Option<f32>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    mag_v: Option<f32>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    mag_r: Option<f32>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    mag_i: Option<f32>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    mag_g: Option<f32>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    mag_j: Option<f32>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    mag_h: Option<f32>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    mag_k: Option<f32>,
+    #[serde(default, borrow, deserialize_with = "de::borrowed_null_marker")]
+    spec_type: Option<&'a str>,
+    #[serde(default, borrow, deserialize_with = "de::borrowed_null_marker")]
+    pretty_name: Option<&'a str>,
+    #[serde(default, borrow, deserialize_with = "de::borrowed_null_marker")]
+    var_type: Option<&'a str>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    var_period: Option<f32>,
+    #[serde(default, deserialize_with = "de::borrowed_parse")]
+    var_amplitude: Option<f32>
+}
+
+impl From<SlimRecord<'_>> for Record {
+    fn from(slim: SlimRecord<'_>) -> Self {
+        let fallback = |coord: Option<&str>| match slim.coord1.map(parse::parse_coordinate_column) {
+            Some(Ok(_)) => None,
+            _ => coord.map(str::to_string)
+        };
+        Self {
+            line: 0,
+            extra: vec![],
+            id: slim.id,
+            identifier: slim.identifier.to_string(),
+            typ: slim.typ.to_string(),
+            ids: slim.ids.map(str::to_string),
+            coord1: slim.coord1.map(str::to_string),
+            coord2: fallback(slim.coord2),
+            coord3: fallback(slim.coord3),
+            coord4: fallback(slim.coord4),
+            coo_err_maj: slim.coo_err_maj,
+            coo_err_min: slim.coo_err_min,
+            coo_err_angle: slim.coo_err_angle,
+            pm: slim.pm.map(str::to_string),
+            plx: slim.plx,
+            radvel: slim.radvel,
+            redshift: slim.redshift,
+            cz: None,
+            mag_u: slim.mag_u,
+            mag_b: slim.mag_b,
+            mag_v: slim.mag_v,
+            mag_r: slim.mag_r,
+            mag_i: slim.mag_i,
+            mag_g: slim.mag_g,
+            mag_j: slim.mag_j,
+            mag_h: slim.mag_h,
+            mag_k: slim.mag_k,
+            spec_type: slim.spec_type.map(str::to_string),
+            morph_type: None,
+            ang_size: None,
+            pretty_name: slim.pretty_name.map(str::to_string),
+            var_type: slim.var_type.map(str::to_string),
+            var_period: slim.var_period,
+            var_amplitude: slim.var_amplitude,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{RecordBuilder, SimbadCsv};
+    use crate::{import_with_options, ColumnSet, ImportOptions, ImportWarning};
+
+    #[test]
+    fn minimal_columns_import_the_same_stars_as_all() {
+        let mut fk5_only = RecordBuilder::new(2, "* alf Lyr").coordinates("18 36 56.336 +38 47 01.28").parallax(130.23).build();
+        fk5_only.coord1 = None;
+        let records = vec![
+            RecordBuilder::new(1, "* alf CMa").coordinates("06 45 08.917 -16 42 58.02").galactic_coordinates("227.23 -08.89").parallax(379.21).build(),
+            fk5_only,
+            RecordBuilder::new(3, "HD 172167").fk4_coordinates("18 35 14.7 +38 44 10").parallax(130.23).build(),
+            RecordBuilder::new(4, "NAME Galactic Center").galactic_coordinates("0.00 0.00").parallax(0.12).build()
+        ];
+        let path = SimbadCsv::new(records).write_temp("minimal-columns.csv").unwrap();
+        let all = import_with_options(&path, &ImportOptions::new().columns(ColumnSet::All)).unwrap();
+        let minimal = import_with_options(&path, &ImportOptions::new().columns(ColumnSet::Minimal)).unwrap();
+        assert_eq!(all.records.len(), 4);
+        assert!(all.skipped.is_empty() && minimal.skipped.is_empty());
+        assert_eq!(all.records.iter().map(|s| (s.id, s.pos)).collect::<Vec<_>>(), minimal.records.iter().map(|s| (s.id, s.pos)).collect::<Vec<_>>());
+        assert_eq!(minimal.warnings, [ImportWarning::GalacticCoordinatesOnly { id: 4 }]);
+    }
+}
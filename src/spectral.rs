@@ -0,0 +1,160 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use crate::{Distance, SimbadError};
+
+// `Star::class` of an object SIMBAD gives no spectral type for, e.g. a galaxy. Stars built by hand
+// with an empty class read the same.
+pub const UNKNOWN_SPECTRAL_TYPE: &str = "Unknown";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SpectralClass {
+    O,
+    B,
+    A,
+    F,
+    G,
+    K,
+    M
+}
+
+impl SpectralClass {
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'O' => Some(Self::O),
+            'B' => Some(Self::B),
+            'A' => Some(Self::A),
+            'F' => Some(Self::F),
+            'G' => Some(Self::G),
+            'K' => Some(Self::K),
+            'M' => Some(Self::M),
+            _ => None
+        }
+    }
+
+    pub fn as_char(&self) -> char {
+        match self {
+            Self::O => 'O',
+            Self::B => 'B',
+            Self::A => 'A',
+            Self::F => 'F',
+            Self::G => 'G',
+            Self::K => 'K',
+            Self::M => 'M'
+        }
+    }
+}
+
+impl Display for SpectralClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LuminosityClass {
+    Supergiant,
+    BrightGiant,
+    Giant,
+    Subgiant,
+    Dwarf,
+    Subdwarf
+}
+
+impl LuminosityClass {
+    fn parse_prefix(input: &str) -> Option<Self> {
+        // Longest numerals first so "III" doesn't match as "I".
+        [("VI", Self::Subdwarf), ("IV", Self::Subgiant), ("V", Self::Dwarf), ("III", Self::Giant), ("II", Self::BrightGiant), ("I", Self::Supergiant)]
+            .into_iter()
+            .find(|(prefix, _)| input.starts_with(prefix))
+            .map(|(_, class)| class)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SpectralType {
+    pub class: SpectralClass,
+    pub subclass: Option<f32>,
+    pub luminosity: Option<LuminosityClass>
+}
+
+impl SpectralType {
+    // Position on the O0..M9 sequence, e.g. G2 -> 42.
+    pub fn sequence_index(&self) -> f32 {
+        self.class as u8 as f32 * 10. + self.subclass.unwrap_or(0.)
+    }
+}
+
+impl FromStr for SpectralType {
+    type Err = SimbadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let class = s.chars().next().and_then(SpectralClass::from_char).ok_or(SimbadError::InvalidSpectralType)?;
+        let rest = &s[1..];
+        let digits = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+        let subclass = if digits > 0 { rest[..digits].parse::<f32>().ok() } else { None };
+        let luminosity = LuminosityClass::parse_prefix(rest[digits..].trim_start());
+        Ok(Self {
+            class,
+            subclass,
+            luminosity,
+        })
+    }
+}
+
+impl Display for SpectralType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.class)?;
+        if let Some(subclass) = self.subclass {
+            write!(f, "{}", subclass)?;
+        }
+        if let Some(luminosity) = self.luminosity {
+            write!(f, "{}", match luminosity {
+                LuminosityClass::Supergiant => "I",
+                LuminosityClass::BrightGiant => "II",
+                LuminosityClass::Giant => "III",
+                LuminosityClass::Subgiant => "IV",
+                LuminosityClass::Dwarf => "V",
+                LuminosityClass::Subdwarf => "VI"
+            })?;
+        }
+        Ok(())
+    }
+}
+
+// Main-sequence absolute visual magnitudes (Schmidt-Kaler 1982), keyed by sequence index.
+const MAIN_SEQUENCE_MV: [(f32, f32); 14] = [
+    (5., -5.7),
+    (10., -4.0),
+    (15., -1.2),
+    (20., 0.6),
+    (25., 1.9),
+    (30., 2.7),
+    (35., 3.5),
+    (40., 4.4),
+    (45., 5.1),
+    (50., 5.9),
+    (55., 7.35),
+    (60., 8.8),
+    (65., 12.3),
+    (68., 16.1)
+];
+
+pub fn main_sequence_absolute_magnitude(spec_type: &SpectralType) -> Option<f32> {
+    let index = spec_type.sequence_index();
+    MAIN_SEQUENCE_MV.windows(2).find(|w| index >= w[0].0 && index <= w[1].0).map(|w| {
+        let t = (index - w[0].0) / (w[1].0 - w[0].0);
+        w[0].1 + (w[1].1 - w[0].1) * t
+    })
+}
+
+// Distance modulus against the main-sequence table; ignores extinction. Returns None for
+// unparseable types, types outside the table and stars explicitly classified off the main sequence.
+pub fn estimate_distance_spectroscopic(spec_type: &str, apparent_v: f32) -> Option<Distance> {
+    let spec_type = spec_type.parse::<SpectralType>().ok()?;
+    if spec_type.luminosity.is_some_and(|l| l != LuminosityClass::Dwarf) {
+        return None;
+    }
+    let absolute = main_sequence_absolute_magnitude(&spec_type)?;
+    Some(Distance::from_parsecs(10f32.powf((apparent_v - absolute + 5.) / 5.)))
+}
@@ -0,0 +1,130 @@
+use std::f32::consts::TAU;
+use crate::{Band, Catalog, Distance, Photometry, Provenance, Source, Star, StellarPosition};
+use crate::spectral::{main_sequence_absolute_magnitude, LuminosityClass, SpectralClass, SpectralType};
+
+// Small xorshift64* generator so fake catalogs are reproducible without pulling in `rand`.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // SplitMix64 step so that nearby seeds (0, 1, 2, ...) give unrelated streams.
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        Self { state: (z ^ (z >> 31)).max(1) }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // Uniform in [0, 1).
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    // Uniform in [0, n).
+    pub fn below(&mut self, n: usize) -> usize {
+        (((self.next_u64() >> 32) * n as u64) >> 32) as usize
+    }
+}
+
+// Main-sequence number fractions per class, roughly following a Kroupa IMF in the solar neighbourhood.
+const CLASS_FRACTIONS: [(SpectralClass, f32); 7] = [
+    (SpectralClass::O, 0.00003),
+    (SpectralClass::B, 0.0013),
+    (SpectralClass::A, 0.006),
+    (SpectralClass::F, 0.03),
+    (SpectralClass::G, 0.076),
+    (SpectralClass::K, 0.121),
+    (SpectralClass::M, 0.76567)
+];
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SyntheticOptions {
+    // Scale length of the r² exp(-r/L) distance distribution, in light-years.
+    pub length_scale: f32,
+    // Julian date assigned to every generated star.
+    pub epoch: f64
+}
+
+impl Default for SyntheticOptions {
+    fn default() -> Self {
+        Self {
+            length_scale: 1000.,
+            epoch: crate::J2000
+        }
+    }
+}
+
+impl SyntheticOptions {
+    pub fn length_scale(mut self, length_scale: f32) -> Self {
+        self.length_scale = length_scale;
+        self
+    }
+
+    pub fn epoch(mut self, epoch: f64) -> Self {
+        self.epoch = epoch;
+        self
+    }
+}
+
+pub fn spectral_type(rng: &mut Rng) -> SpectralType {
+    let mut u = rng.next_f32();
+    let class = CLASS_FRACTIONS.iter().find(|(_, fraction)| {
+        u -= fraction;
+        u < 0.
+    }).map_or(SpectralClass::M, |(class, _)| *class);
+    // Keep within the range covered by the main-sequence magnitude table (O5..M8).
+    let subclass = match class {
+        SpectralClass::O => 5 + rng.below(5),
+        SpectralClass::M => rng.below(9),
+        _ => rng.below(10)
+    };
+    SpectralType {
+        class,
+        subclass: Some(subclass as f32),
+        luminosity: Some(LuminosityClass::Dwarf)
+    }
+}
+
+// Gamma(3, L) sample, i.e. the exponentially decreasing space density prior.
+pub fn distance(rng: &mut Rng, length_scale: f32) -> Distance {
+    let product = (0..3).map(|_| 1. - rng.next_f32()).product::<f32>();
+    Distance::from_light_years(-length_scale * product.ln())
+}
+
+// Uniform on the sphere: (right ascension, declination) in radians.
+pub fn sky_position(rng: &mut Rng) -> (f32, f32) {
+    (rng.next_f32() * TAU, (2. * rng.next_f32() - 1.).asin())
+}
+
+pub fn generate(n: usize, rng: &mut Rng) -> Catalog {
+    generate_with_options(n, rng, &SyntheticOptions::default())
+}
+
+pub fn generate_with_options(n: usize, rng: &mut Rng, options: &SyntheticOptions) -> Catalog {
+    let stars = (0..n).map(|id| {
+        let spec_type = spectral_type(rng);
+        let distance = distance(rng, options.length_scale);
+        let (ra, dec) = sky_position(rng);
+        let v = main_sequence_absolute_magnitude(&spec_type).map(|mv| mv + 5. * distance.parsecs().log10() - 5.);
+        Star {
+            id,
+            pos: StellarPosition::new(distance.light_years(), ra, dec).into(),
+            identifier: format!("SYN {}", id + 1),
+            class: spec_type.to_string(),
+            photometry: Photometry::default().with(Band::V, v),
+            epoch: options.epoch,
+            provenance: vec![Provenance::new(Source::Catalog("synthetic".to_string()), Some(id as u64))],
+            ..Star::default()
+        }
+    }).collect();
+    Catalog::new(stars)
+}
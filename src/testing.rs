@@ -0,0 +1,227 @@
+use std::path::{Path, PathBuf};
+use crate::{write_records, Band, ProperMotion, Record};
+
+// Helpers for parser regression tests: build `Record`s in code, render them as SIMBAD export
+// text and compare results against checked-in golden files. Compiled for this crate's own tests
+// and, for downstream crates, behind the `testing` feature.
+
+// Starts from a plain star ("*") with only an id and identifier; everything else is missing
+// until set.
+#[derive(Debug)]
+pub struct RecordBuilder {
+    record: Record
+}
+
+impl RecordBuilder {
+    pub fn new(id: usize, identifier: &str) -> Self {
+        Self {
+            record: Record {
+                line: 0,
+                extra: vec![],
+                id,
+                identifier: identifier.to_string(),
+                typ: "*".to_string(),
+                ids: None,
+                coord1: None,
+                coord2: None,
+                coord3: None,
+                coord4: None,
+                coo_err_maj: None,
+                coo_err_min: None,
+                coo_err_angle: None,
+                pm: None,
+                plx: None,
+                radvel: None,
+                redshift: None,
+                cz: None,
+                mag_u: None,
+                mag_b: None,
+                mag_v: None,
+                mag_r: None,
+                mag_i: None,
+                mag_g: None,
+                mag_j: None,
+                mag_h: None,
+                mag_k: None,
+                spec_type: None,
+                morph_type: None,
+                ang_size: None,
+                pretty_name: None,
+                var_type: None,
+                var_period: None,
+                var_amplitude: None,
+            }
+        }
+    }
+
+    pub fn object_type(mut self, typ: &str) -> Self {
+        self.record.typ = typ.to_string();
+        self
+    }
+
+    pub fn ids(mut self, ids: &[&str]) -> Self {
+        self.record.ids = Some(ids.join("|"));
+        self
+    }
+
+    // "06 45 08.917 -16 42 58.02", used for both the ICRS and the FK5 column.
+    pub fn coordinates(mut self, sexagesimal: &str) -> Self {
+        self.record.coord1 = Some(sexagesimal.to_string());
+        self.record.coord2 = Some(sexagesimal.to_string());
+        self
+    }
+
+    // "06 42 56.7 -16 38 46" in the FK4 B1950 column.
+    pub fn fk4_coordinates(mut self, sexagesimal: &str) -> Self {
+        self.record.coord3 = Some(sexagesimal.to_string());
+        self
+    }
+
+    // "227.23 -08.89", galactic l and b in degrees.
+    pub fn galactic_coordinates(mut self, degrees: &str) -> Self {
+        self.record.coord4 = Some(degrees.to_string());
+        self
+    }
+
+    // Error ellipse axes in mas and position angle in degrees.
+    pub fn coordinate_uncertainty(mut self, maj_mas: f32, min_mas: f32, pa_deg: f32) -> Self {
+        self.record.coo_err_maj = Some(maj_mas);
+        self.record.coo_err_min = Some(min_mas);
+        self.record.coo_err_angle = Some(pa_deg);
+        self
+    }
+
+    pub fn parallax(mut self, mas: f32) -> Self {
+        self.record.plx = Some(mas);
+        self
+    }
+
+    // mas/yr, RA component already multiplied by cos(dec).
+    pub fn proper_motion(mut self, ra: f32, dec: f32) -> Self {
+        self.record.pm = Some(ProperMotion::new(ra, dec).to_string());
+        self
+    }
+
+    pub fn radial_velocity(mut self, km_s: f32) -> Self {
+        self.record.radvel = Some(km_s);
+        self
+    }
+
+    pub fn magnitude(mut self, band: Band, magnitude: f32) -> Self {
+        let field = match band {
+            Band::U => &mut self.record.mag_u,
+            Band::B => &mut self.record.mag_b,
+            Band::V => &mut self.record.mag_v,
+            Band::R => &mut self.record.mag_r,
+            Band::I => &mut self.record.mag_i,
+            Band::G => &mut self.record.mag_g,
+            Band::J => &mut self.record.mag_j,
+            Band::H => &mut self.record.mag_h,
+            Band::K => &mut self.record.mag_k
+        };
+        *field = Some(magnitude);
+        self
+    }
+
+    pub fn spectral_type(mut self, spec_type: &str) -> Self {
+        self.record.spec_type = Some(spec_type.to_string());
+        self
+    }
+
+    pub fn morph_type(mut self, morph_type: &str) -> Self {
+        self.record.morph_type = Some(morph_type.to_string());
+        self
+    }
+
+    pub fn pretty_name(mut self, name: &str) -> Self {
+        self.record.pretty_name = Some(name.to_string());
+        self
+    }
+
+    pub fn variability(mut self, var_type: &str, period_days: Option<f32>, amplitude: Option<f32>) -> Self {
+        self.record.var_type = Some(var_type.to_string());
+        self.record.var_period = period_days;
+        self.record.var_amplitude = amplitude;
+        self
+    }
+
+    pub fn build(self) -> Record {
+        self.record
+    }
+}
+
+// Export text as SIMBAD's web interface produces it, optionally with the query echo before the
+// header and the row count footer after the data.
+#[derive(Debug, Default)]
+pub struct SimbadCsv {
+    records: Vec<Record>,
+    preamble: Vec<String>,
+    footer: bool
+}
+
+impl SimbadCsv {
+    pub fn new(records: Vec<Record>) -> Self {
+        Self { records, ..Self::default() }
+    }
+
+    pub fn preamble(mut self, lines: &[&str]) -> Self {
+        self.preamble = lines.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    pub fn footer(mut self, footer: bool) -> Self {
+        self.footer = footer;
+        self
+    }
+
+    pub fn render(&self) -> String {
+        let mut text = self.preamble.iter().map(|line| format!("{}\n", line)).collect::<String>();
+        let mut body = vec![];
+        write_records(&self.records, &mut body).expect("writing to a Vec can't fail");
+        text.push_str(&String::from_utf8(body).expect("records are UTF-8"));
+        if self.footer {
+            text.push_str(&format!("\n{} rows\n", self.records.len()));
+        }
+        text
+    }
+
+    // Renders into `path`, for the file based importers.
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.render())
+    }
+
+    // Renders into `name` in the system temp directory, made unique per process so parallel test
+    // binaries don't share files, and returns the path.
+    pub fn write_temp(&self, name: &str) -> std::io::Result<PathBuf> {
+        let path = std::env::temp_dir().join(format!("simbad-{}-{}", std::process::id(), name));
+        self.write(&path)?;
+        Ok(path)
+    }
+}
+
+// Panics with the first differing line unless `actual` matches the golden file. With
+// `UPDATE_GOLDEN` set in the environment the file is (re)written instead.
+pub fn assert_golden<P: AsRef<Path>>(path: P, actual: &str) {
+    let path = path.as_ref();
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("can't create golden file directory");
+        }
+        std::fs::write(path, actual).expect("can't write golden file");
+        return;
+    }
+    let expected = std::fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("can't read golden file {}: {} (set UPDATE_GOLDEN to create it)", path.display(), err));
+    if expected == actual {
+        return;
+    }
+    let (mut expected_lines, mut actual_lines) = (expected.lines(), actual.lines());
+    for line in 1.. {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(e), Some(a)) if e == a => continue,
+            (None, None) => break,
+            (e, a) => panic!("{} differs at line {}:\n expected: {}\n   actual: {}", path.display(), line, e.unwrap_or("<end>"), a.unwrap_or("<end>"))
+        }
+    }
+    panic!("{} differs in line endings or trailing newline", path.display());
+}
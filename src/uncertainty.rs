@@ -0,0 +1,93 @@
+use std::f32::consts::PI;
+use crate::{EquatorialCoordinate, Record, Star, StellarPosition};
+
+// Two positions whose offset is within this many combined standard deviations count as the same
+// object.
+pub const MATCH_SIGMA: f32 = 3.;
+
+const MAS_PER_RADIAN: f32 = 180. * 3600. * 1000. / PI;
+
+// 1σ rounding error of an angle held in f32 radians (one ulp near 2π is ~98 mas, near π/2 ~25 mas),
+// added in quadrature to every error ellipse since no position here is stored more precisely.
+// Without it two Gaia positions a few mas apart would only match if stored bit-identical.
+pub const STORAGE_PRECISION_MAS: f32 = 25.;
+
+// SIMBAD's coordinate error ellipse, taken as 1σ: semi-major and semi-minor axis in
+// milliarcseconds and the position angle of the major axis in degrees east of north.
+#[cfg_attr(feature = "schemars", derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema))]
+#[derive(Default, Copy, Clone, Debug, PartialEq)]
+pub struct CoordinateUncertainty {
+    pub maj_mas: f32,
+    pub min_mas: f32,
+    pub pa_deg: f32
+}
+
+impl CoordinateUncertainty {
+    pub fn new(maj_mas: f32, min_mas: f32, pa_deg: f32) -> Self {
+        Self { maj_mas, min_mas, pa_deg }
+    }
+
+    // None unless the record has both axes; a missing angle is read as 0°.
+    pub fn from_record(record: &Record) -> Option<Self> {
+        Some(Self::new(record.coo_err_maj?, record.coo_err_min?, record.coo_err_angle.unwrap_or(0.)))
+    }
+
+    // (east-east, east-north, north-north) covariance in mas².
+    pub fn covariance(&self) -> [f32; 3] {
+        let (sin, cos) = self.pa_deg.to_radians().sin_cos();
+        let (maj, min) = (self.maj_mas * self.maj_mas, self.min_mas * self.min_mas);
+        [maj * sin * sin + min * cos * cos, (maj - min) * sin * cos, maj * cos * cos + min * sin * sin]
+    }
+}
+
+// Offset from `a` to `b` in units of the combined standard deviation of both positions along
+// that offset (Mahalanobis distance). Uses the tangent plane at `a`, which is fine for anything
+// within a few arcminutes; farther apart the result is simply very large. Each σ includes
+// `STORAGE_PRECISION_MAS`.
+pub fn match_significance(a: &EquatorialCoordinate, a_err: &CoordinateUncertainty, b: &EquatorialCoordinate, b_err: &CoordinateUncertainty) -> f32 {
+    let d_ra = (b.right_ascension as f64 - a.right_ascension as f64 + std::f64::consts::PI).rem_euclid(std::f64::consts::TAU) - std::f64::consts::PI;
+    let east = (d_ra * (a.declination as f64).cos()) as f32 * MAS_PER_RADIAN;
+    let north = (b.declination as f64 - a.declination as f64) as f32 * MAS_PER_RADIAN;
+    let [ea, ena, na] = a_err.covariance();
+    let [eb, enb, nb] = b_err.covariance();
+    let storage = 2. * STORAGE_PRECISION_MAS * STORAGE_PRECISION_MAS;
+    let (ee, en, nn) = (ea + eb + storage, ena + enb, na + nb + storage);
+    let det = ee * nn - en * en;
+    if det <= 0. {
+        return if east == 0. && north == 0. { 0. } else { f32::INFINITY };
+    }
+    ((nn * east * east - 2. * en * east * north + ee * north * north) / det).sqrt()
+}
+
+impl Star {
+    // None unless both stars carry an error ellipse.
+    pub fn match_significance(&self, other: &Star) -> Option<f32> {
+        let a = StellarPosition::from(self.pos).coord;
+        let b = StellarPosition::from(other.pos).coord;
+        Some(match_significance(&a, self.coord_uncertainty.as_ref()?, &b, other.coord_uncertainty.as_ref()?))
+    }
+
+    // Within `MATCH_SIGMA` when both positions have error ellipses, otherwise within the fixed
+    // `tolerance_arcsec`.
+    pub fn matches_position(&self, other: &Star, tolerance_arcsec: f32) -> bool {
+        match self.match_significance(other) {
+            Some(significance) => significance <= MATCH_SIGMA,
+            None => StellarPosition::from(self.pos).coord.approx_eq(&StellarPosition::from(other.pos).coord, tolerance_arcsec)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn precise_positions_a_few_mas_apart_match_despite_f32_storage() {
+        let err = CoordinateUncertainty::new(0.02, 0.02, 0.);
+        let a = EquatorialCoordinate::new(350f32.to_radians(), 0.5f32.to_radians());
+        let b = EquatorialCoordinate::new(a.right_ascension + (5. / MAS_PER_RADIAN), a.declination);
+        assert!(match_significance(&a, &err, &b, &err) < MATCH_SIGMA);
+        let far = EquatorialCoordinate::new(a.right_ascension, a.declination + (1000. / MAS_PER_RADIAN));
+        assert!(match_significance(&a, &err, &far, &err) > MATCH_SIGMA);
+    }
+}
@@ -0,0 +1,34 @@
+use uom::si::angle::second;
+use uom::si::f32::{Angle, Length, Ratio, Velocity};
+use uom::si::length::parsec;
+use uom::si::ratio::ratio;
+use uom::si::velocity::kilometer_per_second;
+use crate::Distance;
+
+impl From<Distance> for Length {
+    fn from(distance: Distance) -> Self {
+        Length::new::<parsec>(distance.parsecs)
+    }
+}
+
+impl From<Length> for Distance {
+    fn from(length: Length) -> Self {
+        Distance::from_parsecs(length.get::<parsec>())
+    }
+}
+
+pub fn parallax(milliarcseconds: f32) -> Angle {
+    Angle::new::<second>(milliarcseconds / 1000.)
+}
+
+pub fn distance_from_parallax(parallax: Angle) -> Length {
+    Length::new::<parsec>(1. / parallax.get::<second>())
+}
+
+pub fn magnitude(value: f32) -> Ratio {
+    Ratio::new::<ratio>(value)
+}
+
+pub fn radial_velocity(km_per_s: f32) -> Velocity {
+    Velocity::new::<kilometer_per_second>(km_per_s)
+}
@@ -0,0 +1,62 @@
+use crate::Record;
+
+const MAX_GALACTIC_VELOCITY: f32 = 1000.; // km/s
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationIssueKind {
+    RightAscensionOutOfRange { column: &'static str, hours: f32 },
+    DeclinationOutOfRange { column: &'static str, degrees: f32 },
+    NegativeParallax(f32),
+    MagnitudeOutOfRange { band: char, value: f32 },
+    // A measurable parallax puts the object in the galaxy, which rules out radial velocities this large.
+    RadialVelocityTooLarge { radvel: f32, plx: f32 },
+    VelocityMismatch { radvel: f32, cz: f32 }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationIssue {
+    pub id: usize,
+    pub kind: ValidationIssueKind
+}
+
+pub fn validate(records: &[Record]) -> Vec<ValidationIssue> {
+    let mut issues = vec![];
+    for record in records {
+        let mut push = |kind| issues.push(ValidationIssue { id: record.id, kind });
+        for (column, coord) in [("coord1", &record.coord1), ("coord2", &record.coord2), ("coord3", &record.coord3)] {
+            let Some((hours, degrees)) = coord.as_deref().and_then(raw_sexagesimal) else { continue };
+            if !(0. ..24.).contains(&hours) {
+                push(ValidationIssueKind::RightAscensionOutOfRange { column, hours });
+            }
+            if !(-90. ..=90.).contains(&degrees) {
+                push(ValidationIssueKind::DeclinationOutOfRange { column, degrees });
+            }
+        }
+        if let Some(plx) = record.plx.filter(|plx| *plx < 0.) {
+            push(ValidationIssueKind::NegativeParallax(plx));
+        }
+        let bands = [('U', record.mag_u), ('B', record.mag_b), ('V', record.mag_v), ('R', record.mag_r), ('I', record.mag_i), ('G', record.mag_g), ('J', record.mag_j), ('H', record.mag_h), ('K', record.mag_k)];
+        for (band, value) in bands {
+            if let Some(value) = value.filter(|m| !(-2. ..=30.).contains(m)) {
+                push(ValidationIssueKind::MagnitudeOutOfRange { band, value });
+            }
+        }
+        if let (Some(radvel), Some(plx)) = (record.radvel, record.plx) && plx > 0. && radvel.abs() > MAX_GALACTIC_VELOCITY {
+            push(ValidationIssueKind::RadialVelocityTooLarge { radvel, plx });
+        }
+        if let (Some(radvel), Some(cz)) = (record.radvel, record.cz) && (radvel - cz).abs() > (0.01 * cz.abs()).max(10.) {
+            push(ValidationIssueKind::VelocityMismatch { radvel, cz });
+        }
+    }
+    issues
+}
+
+// RA in hours and Dec in degrees, without the clamping `EquatorialCoordinate::new` applies.
+fn raw_sexagesimal(input: &str) -> Option<(f32, f32)> {
+    let splits = input.split_whitespace().collect::<Vec<_>>();
+    if splits.len() < 6 { return None; }
+    let hours = splits[0].parse::<f32>().ok()? + splits[1].parse::<f32>().ok()? / 60. + splits[2].parse::<f32>().ok()? / 3600.;
+    let sign = if splits[3].starts_with('-') { -1. } else { 1. };
+    let degrees = splits[3].trim_start_matches(['+', '-']).parse::<f32>().ok()? + splits[4].parse::<f32>().ok()? / 60. + splits[5].parse::<f32>().ok()? / 3600.;
+    Some((hours, sign * degrees))
+}
@@ -0,0 +1,25 @@
+use crate::Record;
+
+#[cfg_attr(feature = "schemars", derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema))]
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct Variability {
+    // GCVS style type, e.g. "DCEP", "RRAB" or "M".
+    pub var_type: String,
+    pub period_days: Option<f32>,
+    // Peak-to-peak, in magnitudes.
+    pub amplitude: Option<f32>
+}
+
+impl Variability {
+    // None unless the record has at least one variability column filled.
+    pub fn from_record(record: &Record) -> Option<Self> {
+        if record.var_type.is_none() && record.var_period.is_none() && record.var_amplitude.is_none() {
+            return None;
+        }
+        Some(Self {
+            var_type: record.var_type.clone().unwrap_or_default(),
+            period_days: record.var_period,
+            amplitude: record.var_amplitude,
+        })
+    }
+}
@@ -0,0 +1,143 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use csv::{ReaderBuilder, StringRecord};
+use crate::{preamble, Band, EquatorialCoordinate, RecordSchema, SimbadError};
+
+// Where the columns `RecordView` reads sit in a header, resolved once per file.
+#[derive(Clone, Debug, Default)]
+pub struct RecordLayout {
+    identifier: Option<usize>,
+    coord1: Option<usize>,
+    plx: Option<usize>,
+    // Indexed like `Band::ALL`.
+    mags: [Option<usize>; 9]
+}
+
+impl RecordLayout {
+    pub fn new(header: &StringRecord) -> Self {
+        let schema = RecordSchema::detect(header.iter());
+        let mut layout = Self::default();
+        for (index, column) in header.iter().enumerate() {
+            let slot = match schema.resolve(column) {
+                Some("identifier") => &mut layout.identifier,
+                Some("coord1") => &mut layout.coord1,
+                Some("plx") => &mut layout.plx,
+                Some("mag_u") => &mut layout.mags[Band::U as usize],
+                Some("mag_b") => &mut layout.mags[Band::B as usize],
+                Some("mag_v") => &mut layout.mags[Band::V as usize],
+                Some("mag_r") => &mut layout.mags[Band::R as usize],
+                Some("mag_i") => &mut layout.mags[Band::I as usize],
+                Some("mag_g") => &mut layout.mags[Band::G as usize],
+                Some("mag_j") => &mut layout.mags[Band::J as usize],
+                Some("mag_h") => &mut layout.mags[Band::H as usize],
+                Some("mag_k") => &mut layout.mags[Band::K as usize],
+                _ => continue
+            };
+            slot.get_or_insert(index);
+        }
+        layout
+    }
+
+    pub fn view<'a>(&'a self, row: &'a StringRecord) -> RecordView<'a> {
+        RecordView { row, layout: self }
+    }
+}
+
+// A data row read in place: nothing is copied or parsed until an accessor asks for it, so loops
+// that only need positions, parallaxes and magnitudes don't pay for a `Record` per row. Missing
+// columns, null markers ("~", "--") and values that don't parse all read as None; import through
+// `Catalog` when malformed rows should be reported instead.
+#[derive(Copy, Clone, Debug)]
+pub struct RecordView<'a> {
+    row: &'a StringRecord,
+    layout: &'a RecordLayout
+}
+
+impl<'a> RecordView<'a> {
+    fn field(&self, index: Option<usize>) -> Option<&'a str> {
+        self.row.get(index?).map(str::trim).filter(|v| !matches!(*v, "" | "~" | "--"))
+    }
+
+    fn number(&self, index: Option<usize>) -> Option<f32> {
+        self.field(index)?.parse::<f32>().ok().filter(|v| v.is_finite())
+    }
+
+    pub fn identifier(&self) -> Option<&'a str> {
+        self.field(self.layout.identifier)
+    }
+
+    // Milliarcseconds.
+    pub fn plx(&self) -> Option<f32> {
+        self.number(self.layout.plx)
+    }
+
+    pub fn mag(&self, band: Band) -> Option<f32> {
+        self.number(self.layout.mags[band as usize])
+    }
+
+    // ICRS position from coord1.
+    pub fn coord(&self) -> Option<EquatorialCoordinate> {
+        parse_coord(self.field(self.layout.coord1)?)
+    }
+
+    // Radians, like `EquatorialCoordinate`.
+    pub fn ra(&self) -> Option<f32> {
+        self.coord().map(|coord| coord.right_ascension)
+    }
+
+    pub fn dec(&self) -> Option<f32> {
+        self.coord().map(|coord| coord.declination)
+    }
+}
+
+// Non-allocating reader for the two layouts SIMBAD writes in coord1: "06 45 08.917 -16 42 58.02"
+// (seconds optional) and "101.2871532 -16.7161167" in degrees. `parse_equatorial` accepts more
+// spellings but normalizes into a new string first.
+fn parse_coord(field: &str) -> Option<EquatorialCoordinate> {
+    let mut tokens = [""; 6];
+    let mut count = 0;
+    for token in field.split_whitespace() {
+        *tokens.get_mut(count)? = token;
+        count += 1;
+    }
+    let tokens = &tokens[..count];
+    let (ra, dec) = if count == 2 {
+        (tokens[0].parse::<f32>().ok()?, tokens[1].parse::<f32>().ok()?)
+    } else {
+        let dec_start = tokens.iter().position(|t| t.starts_with(['+', '-']))?;
+        (sexagesimal(&tokens[..dec_start])? * 15., sexagesimal(&tokens[dec_start..])?)
+    };
+    if !(0. ..360.).contains(&ra) || !(-90. ..=90.).contains(&dec) {
+        return None;
+    }
+    Some(EquatorialCoordinate::new(ra.to_radians(), dec.to_radians()))
+}
+
+// Signed "d m [s]" in whole units.
+fn sexagesimal(tokens: &[&str]) -> Option<f32> {
+    let [whole, minutes, rest @ ..] = tokens else { return None };
+    let seconds = match rest {
+        [] => 0.,
+        [seconds] => seconds.parse::<f32>().ok()?,
+        _ => return None
+    };
+    let value = whole.trim_start_matches(['+', '-']).parse::<f32>().ok()? + minutes.parse::<f32>().ok()? / 60. + seconds / 3600.;
+    Some(if whole.starts_with('-') { -value } else { value })
+}
+
+// Calls `f` with a view of every data row of a SIMBAD export, reusing one row buffer. Rows the
+// CSV reader rejects (e.g. a wrong number of fields) end the scan with that error.
+pub fn scan_records<P: AsRef<Path>>(path: P, mut f: impl FnMut(RecordView<'_>)) -> Result<(), Box<dyn std::error::Error>> {
+    let cleaned = preamble::CleanReader::new(BufReader::new(File::open(path)?))?;
+    let mut rdr = ReaderBuilder::new().delimiter(cleaned.delimiter as u8).from_reader(cleaned);
+    if rdr.headers()?.is_empty() {
+        return Err(SimbadError::InvalidFormat.into());
+    }
+    let layout = RecordLayout::new(rdr.headers()?);
+    let mut row = StringRecord::new();
+    while rdr.read_record(&mut row)? {
+        f(layout.view(&row));
+    }
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use std::f64::consts::{PI, TAU};
+use std::ops::Range;
+use crate::{Band, Catalog, EquatorialCoordinate, Observer, Star, StellarPosition};
+use crate::horizontal::sun_coordinate;
+use crate::observer::SIDEREAL_RATE;
+
+// Sun altitude below which the sky counts as dark (nautical twilight).
+const TWILIGHT_ALTITUDE: f32 = -12. * std::f32::consts::PI / 180.;
+const NIGHT_SAMPLE_STEP: f64 = 5. / 1440.;
+
+#[derive(Clone, Debug)]
+pub struct VisibleStar<'a> {
+    pub star: &'a Star,
+    pub max_altitude: f32,
+    // Julian date at which `max_altitude` is reached; the transit itself if it falls into the night.
+    pub best_time: f64,
+    pub transit: f64,
+    pub rise_set: Option<(f64, f64)>
+}
+
+// First dark interval within the 24 hours after `jd`, as Julian dates.
+pub fn night_window(observer: &Observer, jd: f64) -> Option<(f64, f64)> {
+    let is_dark = |t: f64| sun_coordinate(t).to_horizontal(observer, t).altitude < TWILIGHT_ALTITUDE;
+    let samples = (1. / NIGHT_SAMPLE_STEP) as usize;
+    let start = (0..=samples).map(|i| jd + i as f64 * NIGHT_SAMPLE_STEP).find(|t| is_dark(*t))?;
+    let end = (1..=samples).map(|i| start + i as f64 * NIGHT_SAMPLE_STEP).find(|t| !is_dark(*t)).unwrap_or(jd + 1.);
+    Some((start, end))
+}
+
+// Stars brighter than `max_magnitude` that climb above `min_altitude` (radians) during the night
+// starting after `date` (Julian date, e.g. local noon), ordered by the time they are best placed.
+pub fn visible_tonight<'a>(catalog: &'a Catalog, observer: &Observer, date: f64, min_altitude: f32, max_magnitude: f32) -> Vec<VisibleStar<'a>> {
+    let Some((start, end)) = night_window(observer, date) else { return vec![] };
+    let mut visible = catalog.stars.iter()
+        .filter(|star| star.photometry.get(Band::V).is_some_and(|v| v <= max_magnitude))
+        .filter_map(|star| {
+            let coord = StellarPosition::from(star.pos).coord;
+            let transit = coord.transit_after(observer, start);
+            // Altitude peaks at transit and falls off monotonically for up to half a day either side,
+            // so within the night it is highest at the transit or at one of the window edges.
+            let (best_time, max_altitude) = if transit <= end {
+                (transit, coord.culmination_altitude(observer))
+            } else {
+                [start, end].into_iter()
+                    .map(|t| (t, coord.to_horizontal(observer, t).altitude))
+                    .fold((start, f32::NEG_INFINITY), |best, c| if c.1 > best.1 { c } else { best })
+            };
+            (max_altitude >= min_altitude).then(|| VisibleStar {
+                star,
+                max_altitude,
+                best_time,
+                transit,
+                rise_set: coord.rise_set(observer, transit, 0.),
+            })
+        })
+        .collect::<Vec<_>>();
+    visible.sort_by(|a, b| a.best_time.total_cmp(&b.best_time));
+    visible
+}
+
+// The part of the sky that gets above a minimum altitude while it is dark at some point during a
+// range of nights. Kept as the local sidereal times that are dark, so `contains` is exact for any
+// position up to the 5 minute sampling of the night edges.
+#[derive(Clone, Debug)]
+pub struct SkyCoverage {
+    latitude: f32,
+    min_altitude: f32,
+    // Disjoint, sorted (start, end) ranges within [0, 2π), radians.
+    dark_sidereal: Vec<(f64, f64)>
+}
+
+// One night per day from `dates.start` (e.g. local noon) until `dates.end`, Julian dates;
+// `min_altitude` in radians.
+pub fn coverage(observer: &Observer, dates: Range<f64>, min_altitude: f32) -> SkyCoverage {
+    let mut ranges = vec![];
+    let mut date = dates.start;
+    while date < dates.end {
+        if let Some((start, end)) = night_window(observer, date) {
+            let from = observer.local_sidereal_time(start);
+            push_wrapped(&mut ranges, from, from + (end - start) * SIDEREAL_RATE);
+        }
+        date += 1.;
+    }
+    ranges.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut dark_sidereal: Vec<(f64, f64)> = vec![];
+    for (start, end) in ranges {
+        match dark_sidereal.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => dark_sidereal.push((start, end))
+        }
+    }
+    SkyCoverage { latitude: observer.latitude, min_altitude, dark_sidereal }
+}
+
+// Adds [start, end) (radians, `end` may pass 2π) as ranges within [0, 2π).
+fn push_wrapped(ranges: &mut Vec<(f64, f64)>, start: f64, end: f64) {
+    if end - start >= TAU {
+        ranges.push((0., TAU));
+        return;
+    }
+    let (start, end) = (start.rem_euclid(TAU), start.rem_euclid(TAU) + (end - start));
+    if end > TAU {
+        ranges.push((start, TAU));
+        ranges.push((0., end - TAU));
+    } else {
+        ranges.push((start, end));
+    }
+}
+
+impl SkyCoverage {
+    // Declinations that culminate above the minimum altitude, radians (south, north).
+    pub fn declination_range(&self) -> (f32, f32) {
+        let reach = std::f32::consts::FRAC_PI_2 - self.min_altitude;
+        let limit = std::f32::consts::FRAC_PI_2;
+        ((self.latitude - reach).max(-limit), (self.latitude + reach).min(limit))
+    }
+
+    // Local sidereal times with dark sky, radians; equal to the RA on the meridian.
+    pub fn dark_sidereal_ranges(&self) -> &[(f64, f64)] {
+        &self.dark_sidereal
+    }
+
+    // Largest hour angle (radians) at which a declination is still above the minimum altitude;
+    // π if it never drops below, None if it never gets there.
+    pub fn hour_angle_limit(&self, declination: f32) -> Option<f64> {
+        let (sin_lat, cos_lat) = (self.latitude as f64).sin_cos();
+        let (sin_dec, cos_dec) = (declination as f64).sin_cos();
+        let cos_h = ((self.min_altitude as f64).sin() - sin_lat * sin_dec) / (cos_lat * cos_dec);
+        if cos_h > 1. {
+            None
+        } else if cos_h <= -1. {
+            Some(PI)
+        } else {
+            Some(cos_h.acos())
+        }
+    }
+
+    pub fn contains(&self, coord: &EquatorialCoordinate) -> bool {
+        let Some(limit) = self.hour_angle_limit(coord.declination) else { return false };
+        let mut window = vec![];
+        // Above the altitude while the local sidereal time is within `limit` of the RA.
+        push_wrapped(&mut window, coord.right_ascension as f64 - limit, coord.right_ascension as f64 + limit);
+        window.iter().any(|&(start, end)| self.dark_sidereal.iter().any(|&(a, b)| a <= end && start <= b))
+    }
+}
+
+impl Catalog {
+    // Stars observable from `observer` in the dark on at least one night of `dates`; see `coverage`.
+    pub fn observable_from(&self, observer: &Observer, dates: Range<f64>, min_altitude: f32) -> Vec<&Star> {
+        let coverage = coverage(observer, dates, min_altitude);
+        self.stars.iter().filter(|star| coverage.contains(&StellarPosition::from(star.pos).coord)).collect()
+    }
+}
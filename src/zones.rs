@@ -0,0 +1,118 @@
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
+use glam::DVec3;
+use crate::{Catalog, CrossMatch, EquatorialCoordinate, Star, StellarPosition};
+
+// Declination strips of equal height, each sorted by RA (Gray et al. 2007, "The zones
+// algorithm"). A search only touches the strips its radius overlaps and, inside those, one RA
+// slice found by binary search, which makes many small-radius lookups (cross-matching) cheaper
+// than walking HEALPix pixels. Entries are identified by a caller-chosen `usize`, as in
+// `HealpixIndex`; the index is built once and not updated.
+#[derive(Clone, Debug)]
+pub struct ZoneIndex {
+    // Radians.
+    zone_height: f64,
+    zones: Vec<Vec<ZoneEntry>>
+}
+
+#[derive(Copy, Clone, Debug)]
+struct ZoneEntry {
+    right_ascension: f64,
+    id: usize,
+    // Unit vector, in f64 so that arcsecond chords keep their precision.
+    v: DVec3
+}
+
+impl ZoneIndex {
+    // Zones about as high as the typical search radius work best; `zone_height_arcsec` is rounded
+    // so the strips tile -90°..90° exactly.
+    pub fn new<I: IntoIterator<Item = (usize, EquatorialCoordinate)>>(entries: I, zone_height_arcsec: f32) -> Self {
+        let count = (PI / (zone_height_arcsec as f64 / 3600.).to_radians()).ceil().max(1.) as usize;
+        let mut index = Self { zone_height: PI / count as f64, zones: vec![vec![]; count] };
+        for (id, coord) in entries {
+            let zone = index.zone(coord.declination as f64);
+            let right_ascension = (coord.right_ascension as f64).rem_euclid(TAU);
+            index.zones[zone].push(ZoneEntry { right_ascension, id, v: coord.unit_vector().as_dvec3() });
+        }
+        for zone in &mut index.zones {
+            zone.sort_by(|a, b| a.right_ascension.total_cmp(&b.right_ascension));
+        }
+        index
+    }
+
+    // Indexes every star by its position in `catalog.stars`.
+    pub fn build(catalog: &Catalog, zone_height_arcsec: f32) -> Self {
+        Self::new(catalog.stars.iter().enumerate().map(|(i, star)| (i, StellarPosition::from(star.pos).coord)), zone_height_arcsec)
+    }
+
+    pub fn len(&self) -> usize {
+        self.zones.iter().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.zones.iter().all(Vec::is_empty)
+    }
+
+    fn zone(&self, declination: f64) -> usize {
+        (((declination + FRAC_PI_2) / self.zone_height) as usize).min(self.zones.len() - 1)
+    }
+
+    // Calls `f` with the id and separation (radians) of every entry within `radius` of `center`.
+    fn for_each_within(&self, center: &EquatorialCoordinate, radius: f32, mut f: impl FnMut(usize, f32)) {
+        let radius = radius as f64;
+        let declination = center.declination as f64;
+        let c = center.unit_vector().as_dvec3();
+        // Chord length of `radius`, squared.
+        let max_chord = (2. * (radius.min(PI) / 2.).sin()).powi(2);
+        let ra_offset = if declination.abs() + radius >= FRAC_PI_2 { PI } else { (radius.sin() / declination.cos()).min(1.).asin() };
+        let ra = (center.right_ascension as f64).rem_euclid(TAU);
+        let ranges = if ra_offset >= PI {
+            vec![(0., TAU)]
+        } else if ra - ra_offset < 0. {
+            vec![(0., ra + ra_offset), (ra - ra_offset + TAU, TAU)]
+        } else if ra + ra_offset > TAU {
+            vec![(ra - ra_offset, TAU), (0., ra + ra_offset - TAU)]
+        } else {
+            vec![(ra - ra_offset, ra + ra_offset)]
+        };
+        for zone in &self.zones[self.zone(declination - radius)..=self.zone(declination + radius)] {
+            for &(from, to) in &ranges {
+                let start = zone.partition_point(|e| e.right_ascension < from);
+                let end = zone.partition_point(|e| e.right_ascension <= to);
+                for entry in &zone[start..end] {
+                    if (entry.v - c).length_squared() <= max_chord {
+                        f(entry.id, c.cross(entry.v).length().atan2(c.dot(entry.v)) as f32);
+                    }
+                }
+            }
+        }
+    }
+
+    // Ids within `radius` (radians) of `center`, with their separations, nearest first.
+    pub fn within(&self, center: &EquatorialCoordinate, radius: f32) -> Vec<(usize, f32)> {
+        let mut found = vec![];
+        self.for_each_within(center, radius, |id, separation| found.push((id, separation)));
+        found.sort_by(|a, b| a.1.total_cmp(&b.1));
+        found
+    }
+
+    pub fn nearest(&self, center: &EquatorialCoordinate, radius: f32) -> Option<(usize, f32)> {
+        let mut nearest: Option<(usize, f32)> = None;
+        self.for_each_within(center, radius, |id, separation| {
+            if nearest.is_none_or(|(_, best)| separation < best) {
+                nearest = Some((id, separation));
+            }
+        });
+        nearest
+    }
+
+    // Nearest indexed entry within `radius_arcsec` for every star of `stars`: `a` indexes `stars`,
+    // `b` is the id in this index. Positions are compared as they are; see `crossmatch_epochs`
+    // for catalogs at different epochs.
+    pub fn crossmatch(&self, stars: &[Star], radius_arcsec: f32) -> Vec<CrossMatch> {
+        let radius = (radius_arcsec / 3600.).to_radians();
+        stars.iter().enumerate()
+            .filter_map(|(a, star)| self.nearest(&StellarPosition::from(star.pos).coord, radius)
+                .map(|(b, separation)| CrossMatch { a, b, separation }))
+            .collect()
+    }
+}